@@ -0,0 +1,86 @@
+use std::iter;
+
+/// Join the digits together where the first digit is the most significant
+/// and the last digit is the least significant
+pub fn join_digits<T: Copy + Into<usize>>(digits: &[T]) -> usize {
+    digits
+        .iter()
+        .fold(0, |total, &digit| (total * 10) + digit.into())
+}
+
+/// Get the digits of a number, starting from the least significant digit, 0 has no digits
+pub fn digits_reversed(mut number: usize) -> impl Iterator<Item = usize> {
+    iter::from_fn(move || {
+        if number == 0 {
+            return None;
+        }
+
+        let digit = number % 10;
+        number /= 10;
+
+        Some(digit)
+    })
+}
+
+/// Split a number into its digits, starting from the most significant digit
+pub fn split_digits(number: usize) -> Vec<usize> {
+    if number == 0 {
+        return vec![0];
+    }
+
+    let mut digits: Vec<usize> = digits_reversed(number).collect();
+    digits.reverse();
+
+    digits
+}
+
+/// Count how many digits the number is written with
+pub fn num_digits(number: usize) -> u32 {
+    number.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// Add up every digit of the number
+pub fn digit_sum(number: usize) -> usize {
+    digits_reversed(number).sum()
+}
+
+/// Multiply together every digit of the number
+pub fn digit_product(number: usize) -> usize {
+    if number == 0 {
+        return 0;
+    }
+
+    digits_reversed(number).product()
+}
+
+/// Write the digits of the number in the opposite order, any trailing zeros become leading zeros and are lost
+pub fn reverse_number(number: usize) -> usize {
+    digits_reversed(number).fold(0, |total, digit| (total * 10) + digit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{digit_product, digit_sum, join_digits, num_digits, reverse_number, split_digits};
+
+    #[test]
+    fn test_joined_digits() {
+        assert_eq!(join_digits(&[1usize, 2, 3]), 123);
+        assert_eq!(join_digits(&[0u8, 4, 5]), 45);
+    }
+
+    #[test]
+    fn test_split_digits() {
+        assert_eq!(split_digits(789), vec![7, 8, 9]);
+        assert_eq!(split_digits(0), vec![0]);
+
+        assert_eq!(num_digits(0), 1);
+        assert_eq!(num_digits(100), 3);
+    }
+
+    #[test]
+    fn test_digit_arithmetic() {
+        assert_eq!(digit_sum(9_875), 29);
+        assert_eq!(digit_product(236), 36);
+        assert_eq!(reverse_number(1_230), 321);
+    }
+}
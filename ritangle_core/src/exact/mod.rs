@@ -0,0 +1,460 @@
+use std::{
+    f64::consts::PI,
+    fmt::{self, Display},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use num_rational::Rational64;
+
+use crate::digits::num_digits;
+
+pub mod recognise;
+
+/// Split a number into the largest square dividing it and what is left, giving the root of that square.
+/// What is left has no square factor, such as 72 splitting into 6 and 2 as 72 = 6² × 2
+pub fn split_square(mut number: u64) -> (u64, u64) {
+    let mut root = 1;
+
+    let mut factor = 2;
+    while factor <= number / factor {
+        while number.is_multiple_of(factor * factor) {
+            number /= factor * factor;
+            root *= factor;
+        }
+
+        factor += 1;
+    }
+
+    (root, number)
+}
+
+/// Find the greatest common divisor of two numbers
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Convert a rational number to the nearest float
+fn rational_to_f64(rational: Rational64) -> f64 {
+    *rational.numer() as f64 / *rational.denom() as f64
+}
+
+/// A rational multiple of the square root of a number and a power of π, such as 3√2 or 5π/4
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Term {
+    coefficient: Rational64,
+    /// The number under the square root, which has no square factor so that each term can only be written one way
+    radicand: u64,
+    pi_power: i32,
+}
+
+impl Term {
+    /// Create a new term, moving any square factor of the radicand into the coefficient
+    pub fn new(coefficient: Rational64, radicand: u64, pi_power: i32) -> Self {
+        // The root of 0 is 0, which leaves nothing under the root
+        if radicand == 0 {
+            return Self::zero();
+        }
+
+        let (root, radicand) = split_square(radicand);
+        let coefficient = coefficient * Rational64::from_integer(root as i64);
+
+        Self {
+            coefficient,
+            radicand,
+            pi_power,
+        }
+    }
+
+    /// Create the term 0, which has nothing under the root and no π
+    fn zero() -> Self {
+        Self {
+            coefficient: Rational64::from_integer(0),
+            radicand: 1,
+            pi_power: 0,
+        }
+    }
+
+    /// Get the rational part of the term
+    pub fn coefficient(&self) -> Rational64 {
+        self.coefficient
+    }
+
+    /// Get the number under the square root, which is 1 if there is no root
+    pub fn radicand(&self) -> u64 {
+        self.radicand
+    }
+
+    /// Get the power of π, which is 0 if there is no π
+    pub fn pi_power(&self) -> i32 {
+        self.pi_power
+    }
+
+    /// Check if the term is zero
+    pub fn is_zero(&self) -> bool {
+        *self.coefficient.numer() == 0
+    }
+
+    /// Get the nearest float to the term
+    pub fn to_f64(&self) -> f64 {
+        rational_to_f64(self.coefficient) * (self.radicand as f64).sqrt() * PI.powi(self.pi_power)
+    }
+
+    /// Count roughly how many symbols the term is written with, so simpler terms have a lower complexity
+    pub fn complexity(&self) -> u32 {
+        let numerator = num_digits(self.coefficient.numer().unsigned_abs() as usize);
+        let denominator = match *self.coefficient.denom() {
+            1 => 0,
+            denominator => num_digits(denominator as usize),
+        };
+        let root = match self.radicand {
+            1 => 0,
+            radicand => num_digits(radicand as usize),
+        };
+
+        numerator + denominator + root + self.pi_power.unsigned_abs()
+    }
+}
+
+impl Mul for Term {
+    type Output = Term;
+
+    /// Panics if the radicands left after taking out their common factor overflow, as the coefficients do
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return Term::zero();
+        }
+
+        // Both radicands have no square factor, so their common factor is the only square in the product
+        let common = gcd(self.radicand, rhs.radicand);
+        let radicand = (self.radicand / common)
+            .checked_mul(rhs.radicand / common)
+            .expect("the product of the radicands overflows");
+
+        Term::new(
+            self.coefficient * rhs.coefficient * Rational64::from_integer(common as i64),
+            radicand,
+            self.pi_power + rhs.pi_power,
+        )
+    }
+}
+
+impl Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let numerator = self.coefficient.numer().unsigned_abs();
+        let denominator = *self.coefficient.denom();
+
+        let pi = |power: u32| match power {
+            0 => String::new(),
+            1 => "π".to_string(),
+            2 => "π²".to_string(),
+            3 => "π³".to_string(),
+            power => format!("π^{}", power),
+        };
+
+        // Write the top of the fraction, leaving out a 1 in front of a root or π
+        let mut top = String::new();
+        if numerator != 1 || (self.radicand == 1 && self.pi_power <= 0) {
+            top.push_str(&numerator.to_string());
+        }
+        if self.radicand != 1 {
+            top.push_str(&format!("√{}", self.radicand));
+        }
+        if self.pi_power > 0 {
+            top.push_str(&pi(self.pi_power.unsigned_abs()));
+        }
+
+        // A negative power of π goes on the bottom of the fraction
+        let bottom = match (denominator, self.pi_power < 0) {
+            (1, false) => String::new(),
+            (1, true) => pi(self.pi_power.unsigned_abs()),
+            (denominator, false) => denominator.to_string(),
+            (denominator, true) => format!("({}{})", denominator, pi(self.pi_power.unsigned_abs())),
+        };
+
+        let sign = if *self.coefficient.numer() < 0 {
+            "-"
+        } else {
+            ""
+        };
+
+        if bottom.is_empty() {
+            write!(f, "{}{}", sign, top)
+        } else {
+            write!(f, "{}{}/{}", sign, top, bottom)
+        }
+    }
+}
+
+/// An exact value made of rationals, square roots and powers of π, such as 1 + √2 or 9√3/4 + √6.
+/// The terms are kept in one order with like terms combined, so two values are equal exactly when their terms are
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Exact {
+    terms: Vec<Term>,
+}
+
+impl Exact {
+    /// Create a value from its terms, combining any like terms
+    pub fn from_terms(terms: impl IntoIterator<Item = Term>) -> Self {
+        let mut terms: Vec<Term> = terms.into_iter().collect();
+        terms.sort_by_key(|term| (term.pi_power, term.radicand));
+
+        let mut combined: Vec<Term> = Vec::with_capacity(terms.len());
+        for term in terms {
+            match combined.last_mut() {
+                Some(last) if (last.pi_power, last.radicand) == (term.pi_power, term.radicand) => {
+                    last.coefficient += term.coefficient;
+                }
+                _ => combined.push(term),
+            }
+        }
+
+        combined.retain(|term| !term.is_zero());
+
+        Self { terms: combined }
+    }
+
+    /// Create the value 0
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Create a whole number
+    pub fn integer(number: i64) -> Self {
+        Self::rational(number, 1)
+    }
+
+    /// Create a fraction, which panics if the denominator is 0
+    pub fn rational(numerator: i64, denominator: i64) -> Self {
+        Self::from_terms([Term::new(Rational64::new(numerator, denominator), 1, 0)])
+    }
+
+    /// Create the square root of a number, simplified so √12 is 2√3
+    pub fn sqrt(number: u64) -> Self {
+        Self::from_terms([Term::new(Rational64::from_integer(1), number, 0)])
+    }
+
+    /// Create π
+    pub fn pi() -> Self {
+        Self::from_terms([Term::new(Rational64::from_integer(1), 1, 1)])
+    }
+
+    /// Get the terms that are added together to make the value
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    /// Check if the value is zero
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Get the nearest float to the value
+    pub fn to_f64(&self) -> f64 {
+        self.terms.iter().map(Term::to_f64).sum()
+    }
+
+    /// Count roughly how many symbols the value is written with, so simpler values have a lower complexity
+    pub fn complexity(&self) -> u32 {
+        let terms: u32 = self.terms.iter().map(Term::complexity).sum();
+
+        // Each extra term needs a sign between it and the last
+        terms + self.terms.len().saturating_sub(1) as u32
+    }
+
+    /// Raise the value to a whole power
+    pub fn pow(&self, power: u32) -> Self {
+        (0..power).fold(Self::integer(1), |total, _| total * self.clone())
+    }
+
+    /// Get one divided by the value, which can be found for a single term or two terms with the same power of π,
+    /// such as 1 + √2. Returns None for zero or any other value
+    pub fn recip(&self) -> Option<Self> {
+        let one = Rational64::from_integer(1);
+
+        match self.terms.as_slice() {
+            [term] => {
+                // 1 / (c√r π^k) = √r π^-k / (c r)
+                let coefficient =
+                    one / (term.coefficient * Rational64::from_integer(term.radicand as i64));
+
+                Some(Self::from_terms([Term::new(
+                    coefficient,
+                    term.radicand,
+                    -term.pi_power,
+                )]))
+            }
+            [first, second] if first.pi_power == second.pi_power => {
+                // Multiplying by the conjugate a√r - b√s leaves a² r - b² s, which has no root
+                let square = |term: &Term| {
+                    term.coefficient
+                        * term.coefficient
+                        * Rational64::from_integer(term.radicand as i64)
+                };
+                let denominator = square(first) - square(second);
+
+                Some(Self::from_terms([
+                    Term::new(
+                        first.coefficient / denominator,
+                        first.radicand,
+                        -first.pi_power,
+                    ),
+                    Term::new(
+                        -second.coefficient / denominator,
+                        second.radicand,
+                        -second.pi_power,
+                    ),
+                ]))
+            }
+            _ => None,
+        }
+    }
+
+    /// Divide by the other value, which is only possible if the other value has a reciprocal
+    pub fn checked_div(&self, other: &Exact) -> Option<Self> {
+        other.recip().map(|recip| self.clone() * recip)
+    }
+}
+
+impl From<i64> for Exact {
+    fn from(number: i64) -> Self {
+        Self::integer(number)
+    }
+}
+
+impl From<Rational64> for Exact {
+    fn from(rational: Rational64) -> Self {
+        Self::from_terms([Term::new(rational, 1, 0)])
+    }
+}
+
+impl Add for Exact {
+    type Output = Exact;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Exact::from_terms(self.terms.into_iter().chain(rhs.terms))
+    }
+}
+
+impl Neg for Exact {
+    type Output = Exact;
+
+    fn neg(self) -> Self::Output {
+        Exact::from_terms(self.terms.into_iter().map(|term| Term {
+            coefficient: -term.coefficient,
+            ..term
+        }))
+    }
+}
+
+impl Sub for Exact {
+    type Output = Exact;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Exact {
+    type Output = Exact;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Exact::from_terms(
+            self.terms
+                .iter()
+                .flat_map(|&x| rhs.terms.iter().map(move |&y| x * y)),
+        )
+    }
+}
+
+impl Display for Exact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((first, rest)) = self.terms.split_first() else {
+            return write!(f, "0");
+        };
+
+        write!(f, "{}", first)?;
+
+        // Write each sign between the terms rather than as part of them
+        for term in rest {
+            if *term.coefficient.numer() < 0 {
+                let positive = Term {
+                    coefficient: -term.coefficient,
+                    ..*term
+                };
+
+                write!(f, " - {}", positive)?;
+            } else {
+                write!(f, " + {}", term)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_square, Exact};
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(split_square(72), (6, 2));
+        assert_eq!(Exact::sqrt(12).to_string(), "2√3");
+        assert_eq!(Exact::sqrt(12) * Exact::sqrt(3), Exact::integer(6));
+
+        // (1 + √2)² = 3 + 2√2
+        let one_plus_root_two = Exact::integer(1) + Exact::sqrt(2);
+
+        assert_eq!(one_plus_root_two.pow(2).to_string(), "3 + 2√2");
+        assert_eq!(
+            one_plus_root_two.recip().unwrap(),
+            Exact::sqrt(2) - Exact::integer(1)
+        );
+
+        let quarter_pi = Exact::pi().checked_div(&Exact::integer(4)).unwrap();
+
+        assert_eq!(quarter_pi.to_string(), "π/4");
+        assert!((quarter_pi.to_f64() - std::f64::consts::FRAC_PI_4).abs() < 1e-15);
+        assert_eq!(
+            (Exact::rational(-3, 4) * Exact::pi().recip().unwrap()).to_string(),
+            "-3/(4π)"
+        );
+        assert_eq!(
+            (Exact::sqrt(3) * Exact::rational(1, 2) - Exact::pi().pow(2)).to_string(),
+            "√3/2 - π²"
+        );
+        assert!(Exact::zero().recip().is_none());
+    }
+
+    #[test]
+    fn test_zero_root() {
+        assert!(Exact::sqrt(0).is_zero());
+        assert_eq!(Exact::sqrt(0), Exact::zero());
+        assert_eq!(Exact::sqrt(0).to_string(), "0");
+        assert!((Exact::sqrt(2) * Exact::sqrt(0)).is_zero());
+        assert_eq!(
+            (Exact::integer(1) + Exact::sqrt(3)) * Exact::sqrt(0),
+            Exact::zero()
+        );
+        assert_eq!(Exact::sqrt(0) + Exact::sqrt(5), Exact::sqrt(5));
+    }
+
+    #[test]
+    fn test_large_radicands() {
+        let prime = 4_294_967_291;
+
+        // Multiplying the roots takes the common factor out before the radicands can overflow
+        assert_eq!(
+            Exact::sqrt(prime) * Exact::sqrt(prime),
+            Exact::integer(prime as i64)
+        );
+        assert_eq!(
+            (Exact::sqrt(2 * prime) * Exact::sqrt(3 * prime)).to_string(),
+            format!("{}√6", prime)
+        );
+    }
+}
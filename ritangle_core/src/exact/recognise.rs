@@ -0,0 +1,283 @@
+use std::{f64::consts::PI, ops::RangeInclusive};
+
+use num_rational::Rational64;
+
+use super::{split_square, Exact, Term};
+
+/// Find the closest fraction to x with a denominator no larger than the maximum, using continued fractions
+fn best_rational(x: f64, max_denominator: i64) -> Option<Rational64> {
+    if !x.is_finite() || x.abs() > 1e12 {
+        return None;
+    }
+
+    // Each convergent is p1/q1, with the one before it p0/q0
+    let (mut p0, mut q0, mut p1, mut q1) = (0, 1, 1, 0);
+    let mut y = x;
+
+    loop {
+        let whole = y.floor();
+        if whole.abs() > 1e12 {
+            break;
+        }
+
+        let a = whole as i64;
+        let (p2, q2) = (a * p1 + p0, a * q1 + q0);
+
+        if q2 > max_denominator {
+            // The best fraction may be between the last two convergents
+            let k = (max_denominator - q0) / q1;
+            let (p, q) = (k * p1 + p0, k * q1 + q0);
+
+            let error = |p: i64, q: i64| (x - p as f64 / q as f64).abs();
+            if k > 0 && error(p, q) < error(p1, q1) {
+                return Some(Rational64::new(p, q));
+            }
+
+            break;
+        }
+
+        (p0, q0, p1, q1) = (p1, q1, p2, q2);
+
+        let fraction = y - whole;
+        if fraction == 0.0 {
+            break;
+        }
+
+        y = 1.0 / fraction;
+    }
+
+    Some(Rational64::new(p1, q1))
+}
+
+/// Finds the simplest exact value that a float is close to, made of rationals, square roots and powers of π
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recogniser {
+    max_denominator: i64,
+    max_radicand: u64,
+    pi_powers: RangeInclusive<i32>,
+    tolerance: f64,
+    sum_max_radicand: u64,
+    sum_max_denominator: i64,
+    sum_max_coefficient: i64,
+}
+
+impl Default for Recogniser {
+    fn default() -> Self {
+        Self {
+            max_denominator: 1000,
+            max_radicand: 100,
+            pi_powers: -1..=2,
+            tolerance: 1e-11,
+            sum_max_radicand: 30,
+            sum_max_denominator: 24,
+            sum_max_coefficient: 100,
+        }
+    }
+}
+
+impl Recogniser {
+    /// Create a new recogniser with the default limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the largest denominator of a single term
+    pub fn with_max_denominator(mut self, max_denominator: i64) -> Self {
+        self.max_denominator = max_denominator;
+        self
+    }
+
+    /// Set the largest number under a square root
+    pub fn with_max_radicand(mut self, max_radicand: u64) -> Self {
+        self.max_radicand = max_radicand;
+        self
+    }
+
+    /// Set the powers of π to try
+    pub fn with_pi_powers(mut self, pi_powers: RangeInclusive<i32>) -> Self {
+        self.pi_powers = pi_powers;
+        self
+    }
+
+    /// Set how close an exact value must be to the float, relative to the size of the float
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the largest number under either root of a sum of two roots, kept lower than for a single term
+    /// as every pair of roots is tried
+    pub fn with_sum_max_radicand(mut self, sum_max_radicand: u64) -> Self {
+        self.sum_max_radicand = sum_max_radicand;
+        self
+    }
+
+    /// Set the largest denominator shared by a sum of two roots
+    pub fn with_sum_max_denominator(mut self, sum_max_denominator: i64) -> Self {
+        self.sum_max_denominator = sum_max_denominator;
+        self
+    }
+
+    /// Set the largest coefficient of either root in a sum of two roots
+    pub fn with_sum_max_coefficient(mut self, sum_max_coefficient: i64) -> Self {
+        self.sum_max_coefficient = sum_max_coefficient;
+        self
+    }
+
+    /// Check if the exact value is within the tolerance of x
+    fn is_close(&self, exact: &Exact, x: f64) -> bool {
+        (exact.to_f64() - x).abs() <= self.tolerance * x.abs()
+    }
+
+    /// Pick the simplest value, then the closest to x
+    fn simplest(&self, candidates: impl Iterator<Item = Exact>, x: f64) -> Option<Exact> {
+        candidates
+            .filter(|exact| self.is_close(exact, x))
+            .min_by(|p, q| {
+                p.complexity()
+                    .cmp(&q.complexity())
+                    .then_with(|| (p.to_f64() - x).abs().total_cmp(&(q.to_f64() - x).abs()))
+            })
+    }
+
+    /// Find the most likely exact value of x, trying a single term such as 5π/4 before a sum of two roots
+    /// such as 9√3/4 + √6. Returns None if nothing within the limits is close enough
+    pub fn recognise(&self, x: f64) -> Option<Exact> {
+        if !x.is_finite() {
+            return None;
+        }
+
+        if x == 0.0 {
+            return Some(Exact::zero());
+        }
+
+        let radicands: Vec<u64> = (1..=self.max_radicand)
+            .filter(|&radicand| split_square(radicand).0 == 1)
+            .collect();
+
+        // A single term is a fraction of one root and power of π
+        let terms = self.pi_powers.clone().flat_map(|pi_power| {
+            radicands.iter().filter_map(move |&radicand| {
+                let base = (radicand as f64).sqrt() * PI.powi(pi_power);
+                let coefficient = best_rational(x / base, self.max_denominator)?;
+
+                Some(Exact::from_terms([Term::new(
+                    coefficient,
+                    radicand,
+                    pi_power,
+                )]))
+            })
+        });
+
+        if let Some(exact) = self.simplest(terms, x) {
+            return Some(exact);
+        }
+
+        // A sum of two roots over a shared denominator, where the first coefficient follows from the second
+        let sum_radicands: Vec<u64> = radicands
+            .iter()
+            .copied()
+            .filter(|&radicand| radicand <= self.sum_max_radicand)
+            .collect();
+
+        let mut sums = Vec::new();
+        for pi_power in self.pi_powers.clone() {
+            let scaled = x / PI.powi(pi_power);
+
+            for (i, &first) in sum_radicands.iter().enumerate() {
+                for &second in &sum_radicands[i + 1..] {
+                    let (root_first, root_second) = ((first as f64).sqrt(), (second as f64).sqrt());
+
+                    for denominator in 1..=self.sum_max_denominator {
+                        for b in -self.sum_max_coefficient..=self.sum_max_coefficient {
+                            let a = ((scaled * denominator as f64 - b as f64 * root_second)
+                                / root_first)
+                                .round();
+
+                            if b == 0 || a == 0.0 || a.abs() > self.sum_max_coefficient as f64 {
+                                continue;
+                            }
+
+                            let value = (a * root_first + b as f64 * root_second)
+                                * PI.powi(pi_power)
+                                / denominator as f64;
+
+                            if (value - x).abs() <= self.tolerance * x.abs() {
+                                sums.push(Exact::from_terms([
+                                    Term::new(
+                                        Rational64::new(a as i64, denominator),
+                                        first,
+                                        pi_power,
+                                    ),
+                                    Term::new(Rational64::new(b, denominator), second, pi_power),
+                                ]));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        self.simplest(sums.into_iter(), x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{E, PI};
+
+    use super::Recogniser;
+
+    #[test]
+    fn test_recognise() {
+        let recogniser = Recogniser::new();
+        let recognise = |x: f64| recogniser.recognise(x).map(|exact| exact.to_string());
+
+        assert_eq!(recognise(2f64.sqrt()).as_deref(), Some("√2"));
+        assert_eq!(recognise(-2.0 / 3.0).as_deref(), Some("-2/3"));
+        assert_eq!(recognise(1.25 * PI).as_deref(), Some("5π/4"));
+        assert_eq!(recognise(PI * PI / 6.0).as_deref(), Some("π²/6"));
+        assert_eq!(recognise(3.0 / (4.0 * PI)).as_deref(), Some("3/(4π)"));
+
+        // Sums of two roots
+        assert_eq!(recognise(1.0 + 2f64.sqrt()).as_deref(), Some("1 + √2"));
+        assert_eq!(
+            recognise((9.0 * 3f64.sqrt() + 4.0 * 6f64.sqrt()) / 4.0).as_deref(),
+            Some("9√3/4 + √6")
+        );
+
+        assert_eq!(recognise(E), None);
+    }
+
+    #[test]
+    fn test_sum_limits() {
+        let x = (9.0 * 3f64.sqrt() + 4.0 * 6f64.sqrt()) / 4.0;
+
+        // Each limit below what 9√3/4 + √6 needs leaves nothing rather than a worse fit
+        assert_eq!(
+            Recogniser::new().with_sum_max_radicand(5).recognise(x),
+            None
+        );
+        assert_eq!(
+            Recogniser::new().with_sum_max_denominator(3).recognise(x),
+            None
+        );
+        assert_eq!(
+            Recogniser::new().with_sum_max_coefficient(8).recognise(x),
+            None
+        );
+
+        // Raising the limits finds sums the defaults do not try
+        let x = 3.0 * 31f64.sqrt() / 25.0 + 2f64.sqrt();
+        assert_eq!(Recogniser::new().recognise(x), None);
+        assert_eq!(
+            Recogniser::new()
+                .with_sum_max_radicand(31)
+                .with_sum_max_denominator(25)
+                .recognise(x)
+                .map(|exact| exact.to_string())
+                .as_deref(),
+            Some("√2 + 3√31/25")
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt::{self, Display};
+
+use super::{
+    great_circle::Position,
+    plus_code::{PlusCode, PlusCodeError},
+};
+
+/// The side of the equator or the prime meridian an angle is measured towards
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Hemisphere {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Hemisphere {
+    /// Check if the hemisphere gives a latitude, rather than a longitude
+    pub fn is_latitude(&self) -> bool {
+        matches!(self, Hemisphere::North | Hemisphere::South)
+    }
+
+    /// Get the sign of an angle towards this hemisphere in decimal degrees,
+    /// where north and east are positive
+    fn sign(&self) -> f64 {
+        match self {
+            Hemisphere::North | Hemisphere::East => 1.0,
+            Hemisphere::South | Hemisphere::West => -1.0,
+        }
+    }
+
+    /// Get the letter used to write the hemisphere
+    pub fn letter(&self) -> char {
+        match self {
+            Hemisphere::North => 'N',
+            Hemisphere::South => 'S',
+            Hemisphere::East => 'E',
+            Hemisphere::West => 'W',
+        }
+    }
+}
+
+/// The reasons an angle or coordinate cannot be created
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoordinateError {
+    /// There are only 60 minutes in a degree
+    MinutesOutOfRange(u32),
+    /// A latitude is at most 90 degrees and a longitude is at most 180 degrees
+    DegreesOutOfRange(u32),
+    /// The latitude was given east or west, or the longitude was given north or south
+    WrongHemisphere(Hemisphere),
+}
+
+impl Display for CoordinateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoordinateError::MinutesOutOfRange(minutes) => {
+                write!(f, "{} minutes is not below 60", minutes)
+            }
+            CoordinateError::DegreesOutOfRange(degrees) => {
+                write!(f, "{} degrees is out of range", degrees)
+            }
+            CoordinateError::WrongHemisphere(hemisphere) => {
+                write!(f, "{:?} is the wrong hemisphere for this angle", hemisphere)
+            }
+        }
+    }
+}
+
+/// An angle in whole degrees and minutes towards a hemisphere, such as 36°12'N
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DegreesMinutes {
+    degrees: u32,
+    minutes: u32,
+    hemisphere: Hemisphere,
+}
+
+impl DegreesMinutes {
+    /// Create a new angle, the minutes must be below 60 and the angle must not pass the pole
+    /// for a latitude or the antimeridian for a longitude
+    pub fn new(
+        degrees: u32,
+        minutes: u32,
+        hemisphere: Hemisphere,
+    ) -> Result<Self, CoordinateError> {
+        if minutes >= 60 {
+            return Err(CoordinateError::MinutesOutOfRange(minutes));
+        }
+
+        let max_degrees = if hemisphere.is_latitude() { 90 } else { 180 };
+
+        // The largest angle has no minutes past it
+        if degrees > max_degrees || (degrees == max_degrees && minutes > 0) {
+            return Err(CoordinateError::DegreesOutOfRange(degrees));
+        }
+
+        Ok(Self {
+            degrees,
+            minutes,
+            hemisphere,
+        })
+    }
+
+    /// Get the whole degrees of the angle
+    pub fn degrees(&self) -> u32 {
+        self.degrees
+    }
+
+    /// Get the minutes past the whole degrees
+    pub fn minutes(&self) -> u32 {
+        self.minutes
+    }
+
+    /// Get the hemisphere the angle is towards
+    pub fn hemisphere(&self) -> Hemisphere {
+        self.hemisphere
+    }
+
+    /// Convert to decimal degrees, where north and east are positive
+    pub fn to_decimal(&self) -> f64 {
+        self.hemisphere.sign() * (self.degrees as f64 + (self.minutes as f64 / 60.0))
+    }
+}
+
+impl Display for DegreesMinutes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}°{:02}'{}",
+            self.degrees,
+            self.minutes,
+            self.hemisphere.letter()
+        )
+    }
+}
+
+/// A position on the Earth given as a latitude and a longitude
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Coordinate {
+    latitude: DegreesMinutes,
+    longitude: DegreesMinutes,
+}
+
+impl Coordinate {
+    /// Create a new coordinate, the latitude must be north or south and the longitude east or west
+    pub fn new(
+        latitude: DegreesMinutes,
+        longitude: DegreesMinutes,
+    ) -> Result<Self, CoordinateError> {
+        if !latitude.hemisphere().is_latitude() {
+            return Err(CoordinateError::WrongHemisphere(latitude.hemisphere()));
+        }
+
+        if longitude.hemisphere().is_latitude() {
+            return Err(CoordinateError::WrongHemisphere(longitude.hemisphere()));
+        }
+
+        Ok(Self {
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Get the latitude in degrees and minutes
+    pub fn latitude_dm(&self) -> DegreesMinutes {
+        self.latitude
+    }
+
+    /// Get the longitude in degrees and minutes
+    pub fn longitude_dm(&self) -> DegreesMinutes {
+        self.longitude
+    }
+
+    /// Get the latitude in decimal degrees, where north is positive
+    pub fn latitude(&self) -> f64 {
+        self.latitude.to_decimal()
+    }
+
+    /// Get the longitude in decimal degrees, where east is positive
+    pub fn longitude(&self) -> f64 {
+        self.longitude.to_decimal()
+    }
+
+    /// Get the coordinate as a position in decimal degrees
+    pub fn position(&self) -> Position {
+        Position::new(self.latitude(), self.longitude())
+    }
+
+    /// Find the distance in kilometres to the other coordinate along a great circle of a spherical Earth
+    pub fn haversine_distance(&self, other: &Coordinate) -> f64 {
+        self.position().haversine_distance(&other.position())
+    }
+
+    /// Find the distance in kilometres to the other coordinate along the WGS 84 ellipsoid,
+    /// returns None if Vincenty's formula does not converge
+    pub fn vincenty_distance(&self, other: &Coordinate) -> Option<f64> {
+        self.position().vincenty_distance(&other.position())
+    }
+
+    /// Find the direction to set off in towards the other coordinate, in degrees clockwise from north
+    pub fn initial_bearing(&self, other: &Coordinate) -> f64 {
+        self.position().initial_bearing(&other.position())
+    }
+
+    /// Find the position halfway to the other coordinate along a great circle,
+    /// which is not usually a whole number of minutes
+    pub fn midpoint(&self, other: &Coordinate) -> Position {
+        self.position().midpoint(&other.position())
+    }
+
+    /// Encode the coordinate as a full plus code with the number of digits provided
+    pub fn plus_code(&self, code_length: usize) -> Result<PlusCode, PlusCodeError> {
+        PlusCode::encode(self.latitude(), self.longitude(), code_length)
+    }
+}
+
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.latitude, self.longitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Coordinate, CoordinateError, DegreesMinutes, Hemisphere};
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(
+            DegreesMinutes::new(36, 60, Hemisphere::North),
+            Err(CoordinateError::MinutesOutOfRange(60))
+        );
+        assert_eq!(
+            DegreesMinutes::new(91, 0, Hemisphere::South),
+            Err(CoordinateError::DegreesOutOfRange(91))
+        );
+        assert_eq!(
+            DegreesMinutes::new(90, 1, Hemisphere::North),
+            Err(CoordinateError::DegreesOutOfRange(90))
+        );
+        assert!(DegreesMinutes::new(180, 0, Hemisphere::West).is_ok());
+
+        let north = DegreesMinutes::new(36, 12, Hemisphere::North).unwrap();
+        let west = DegreesMinutes::new(86, 46, Hemisphere::West).unwrap();
+
+        assert_eq!(
+            Coordinate::new(west, north),
+            Err(CoordinateError::WrongHemisphere(Hemisphere::West))
+        );
+    }
+
+    #[test]
+    fn test_to_decimal() {
+        let coordinate = Coordinate::new(
+            DegreesMinutes::new(36, 12, Hemisphere::North).unwrap(),
+            DegreesMinutes::new(86, 46, Hemisphere::West).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(coordinate.latitude(), 36.2);
+        assert_eq!(coordinate.longitude(), -(86.0 + 46.0 / 60.0));
+        assert_eq!(coordinate.to_string(), "36°12'N 86°46'W");
+    }
+}
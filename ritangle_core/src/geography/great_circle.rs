@@ -0,0 +1,232 @@
+use std::fmt::{self, Display};
+
+/// The mean radius of the Earth in kilometres
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// The kilometres in a statute mile
+pub const KM_PER_MILE: f64 = 1.609344;
+
+/// The radius at the equator and the flattening of the WGS 84 ellipsoid
+const WGS84_SEMI_MAJOR_AXIS_KM: f64 = 6378.137;
+const WGS84_FLATTENING: f64 = 1.0 / 298.257223563;
+
+/// The most iterations of Vincenty's formula before giving up, it only fails to converge for nearly antipodal points
+const VINCENTY_MAX_ITERATIONS: usize = 200;
+
+/// A point on the Earth in decimal degrees, where north and east are positive
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl Position {
+    /// Create a new position from decimal degrees
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+
+    /// Get the latitude, where north is positive
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /// Get the longitude, where east is positive
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Find the distance in kilometres to the other position along a great circle of a spherical Earth
+    pub fn haversine_distance(&self, other: &Position) -> f64 {
+        let (latitude, other_latitude) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let latitude_change = other_latitude - latitude;
+        let longitude_change = (other.longitude - self.longitude).to_radians();
+
+        let a = (latitude_change / 2.0).sin().powi(2)
+            + latitude.cos() * other_latitude.cos() * (longitude_change / 2.0).sin().powi(2);
+
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+
+    /// Find the distance in kilometres to the other position along the WGS 84 ellipsoid using Vincenty's formula,
+    /// which is accurate to under a millimetre. Returns None if the formula does not converge
+    pub fn vincenty_distance(&self, other: &Position) -> Option<f64> {
+        let semi_minor_axis = WGS84_SEMI_MAJOR_AXIS_KM * (1.0 - WGS84_FLATTENING);
+
+        // The latitudes on the auxiliary sphere
+        let reduced =
+            |latitude: f64| ((1.0 - WGS84_FLATTENING) * latitude.to_radians().tan()).atan();
+        let (u1, u2) = (reduced(self.latitude), reduced(other.latitude));
+        let (sin_u1, cos_u1) = u1.sin_cos();
+        let (sin_u2, cos_u2) = u2.sin_cos();
+
+        let longitude_change = (other.longitude - self.longitude).to_radians();
+
+        // Repeat until the longitude on the auxiliary sphere stops changing
+        let mut lambda = longitude_change;
+        for _ in 0..VINCENTY_MAX_ITERATIONS {
+            let (sin_lambda, cos_lambda) = lambda.sin_cos();
+
+            let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+                + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+            .sqrt();
+
+            // The positions are the same
+            if sin_sigma == 0.0 {
+                return Some(0.0);
+            }
+
+            let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+            let sigma = sin_sigma.atan2(cos_sigma);
+
+            let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+            let cos_sq_alpha = 1.0 - sin_alpha.powi(2);
+
+            // Both positions are on the equator
+            let cos_2_sigma_m = if cos_sq_alpha == 0.0 {
+                0.0
+            } else {
+                cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha
+            };
+
+            let c = WGS84_FLATTENING / 16.0
+                * cos_sq_alpha
+                * (4.0 + WGS84_FLATTENING * (4.0 - 3.0 * cos_sq_alpha));
+
+            let previous_lambda = lambda;
+            lambda = longitude_change
+                + (1.0 - c)
+                    * WGS84_FLATTENING
+                    * sin_alpha
+                    * (sigma
+                        + c * sin_sigma
+                            * (cos_2_sigma_m
+                                + c * cos_sigma * (-1.0 + 2.0 * cos_2_sigma_m.powi(2))));
+
+            if (lambda - previous_lambda).abs() < 1e-12 {
+                let u_sq = cos_sq_alpha
+                    * (WGS84_SEMI_MAJOR_AXIS_KM.powi(2) - semi_minor_axis.powi(2))
+                    / semi_minor_axis.powi(2);
+
+                let a = 1.0
+                    + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+                let b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+
+                let delta_sigma = b
+                    * sin_sigma
+                    * (cos_2_sigma_m
+                        + b / 4.0
+                            * (cos_sigma * (-1.0 + 2.0 * cos_2_sigma_m.powi(2))
+                                - b / 6.0
+                                    * cos_2_sigma_m
+                                    * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                    * (-3.0 + 4.0 * cos_2_sigma_m.powi(2))));
+
+                return Some(semi_minor_axis * a * (sigma - delta_sigma));
+            }
+        }
+
+        None
+    }
+
+    /// Find the direction to set off in towards the other position along a great circle,
+    /// in degrees clockwise from north between 0 and 360
+    pub fn initial_bearing(&self, other: &Position) -> f64 {
+        let (latitude, other_latitude) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let longitude_change = (other.longitude - self.longitude).to_radians();
+
+        let y = longitude_change.sin() * other_latitude.cos();
+        let x = latitude.cos() * other_latitude.sin()
+            - latitude.sin() * other_latitude.cos() * longitude_change.cos();
+
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+
+    /// Find the position halfway to the other position along a great circle
+    pub fn midpoint(&self, other: &Position) -> Position {
+        let (latitude, other_latitude) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let longitude = self.longitude.to_radians();
+        let longitude_change = (other.longitude - self.longitude).to_radians();
+
+        let bx = other_latitude.cos() * longitude_change.cos();
+        let by = other_latitude.cos() * longitude_change.sin();
+
+        let midpoint_latitude = (latitude.sin() + other_latitude.sin())
+            .atan2(((latitude.cos() + bx).powi(2) + by.powi(2)).sqrt());
+        let midpoint_longitude = longitude + by.atan2(latitude.cos() + bx);
+
+        // Keep the longitude between -180 and 180
+        Position::new(
+            midpoint_latitude.to_degrees(),
+            (midpoint_longitude.to_degrees() + 540.0).rem_euclid(360.0) - 180.0,
+        )
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.4}, {:.4}", self.latitude, self.longitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Position;
+
+    /// Convert degrees, minutes and seconds to decimal degrees
+    fn dms(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+        degrees.signum() * (degrees.abs() + minutes / 60.0 + seconds / 3600.0)
+    }
+
+    #[test]
+    fn test_distance() {
+        // A degree along the equator
+        let distance = Position::new(0.0, 0.0).haversine_distance(&Position::new(0.0, 1.0));
+
+        assert!((distance - 111.195).abs() < 1e-3, "{} km", distance);
+
+        // Flinders Peak to Buninyong, the worked example of Vincenty's paper
+        let flinders_peak = Position::new(dms(-37.0, 57.0, 3.7203), dms(144.0, 25.0, 29.5244));
+        let buninyong = Position::new(dms(-37.0, 39.0, 10.1561), dms(143.0, 55.0, 35.3839));
+
+        let distance = flinders_peak.vincenty_distance(&buninyong).unwrap();
+
+        assert!((distance - 54.972271).abs() < 1e-6, "{} km", distance);
+        assert_eq!(flinders_peak.vincenty_distance(&flinders_peak), Some(0.0));
+
+        // The sphere is within half a percent of the ellipsoid
+        let haversine = flinders_peak.haversine_distance(&buninyong);
+        assert!((haversine - distance).abs() / distance < 0.005);
+    }
+
+    #[test]
+    fn test_bearing_and_midpoint() {
+        let origin = Position::new(0.0, 0.0);
+
+        assert!((origin.initial_bearing(&Position::new(0.0, 1.0)) - 90.0).abs() < 1e-9);
+        assert!((origin.initial_bearing(&Position::new(1.0, 0.0))).abs() < 1e-9);
+        assert!((origin.initial_bearing(&Position::new(0.0, -1.0)) - 270.0).abs() < 1e-9);
+
+        // Flinders Peak to Buninyong sets off at 306°52'05.37" on the ellipsoid,
+        // which the sphere is within a fifth of a degree of
+        let flinders_peak = Position::new(dms(-37.0, 57.0, 3.7203), dms(144.0, 25.0, 29.5244));
+        let buninyong = Position::new(dms(-37.0, 39.0, 10.1561), dms(143.0, 55.0, 35.3839));
+
+        let bearing = flinders_peak.initial_bearing(&buninyong);
+        assert!(
+            (bearing - dms(306.0, 52.0, 5.37)).abs() < 0.2,
+            "{}",
+            bearing
+        );
+
+        let midpoint = Position::new(0.0, 170.0).midpoint(&Position::new(0.0, -170.0));
+        assert!(midpoint.latitude().abs() < 1e-9);
+        assert!((midpoint.longitude().abs() - 180.0).abs() < 1e-9);
+
+        let midpoint = Position::new(10.0, 0.0).midpoint(&Position::new(-10.0, 0.0));
+        assert!(midpoint.latitude().abs() < 1e-9 && midpoint.longitude().abs() < 1e-9);
+    }
+}
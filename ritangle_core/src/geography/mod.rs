@@ -0,0 +1,3 @@
+pub mod coordinate;
+pub mod great_circle;
+pub mod plus_code;
@@ -0,0 +1,491 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// The digits used by Open Location Codes, in order of value
+const ALPHABET: &[u8; 20] = b"23456789CFGHJMPQRVWX";
+
+/// The base of the pairs of digits
+const ENCODING_BASE: i64 = 20;
+
+/// The character between the first eight digits and the rest
+const SEPARATOR: char = '+';
+
+/// The number of digits before the separator in a full code
+const SEPARATOR_POSITION: usize = 8;
+
+/// The character that pads codes with fewer than eight digits up to the separator
+const PADDING: char = '0';
+
+/// The number of digits given as pairs of latitude and longitude,
+/// after these each digit refines a grid of 5 rows and 4 columns
+const PAIR_CODE_LENGTH: usize = 10;
+
+/// The most digits that are used, any digits after these are ignored
+const MAX_DIGIT_COUNT: usize = 15;
+
+/// The number of digits after the pairs
+const GRID_CODE_LENGTH: u32 = (MAX_DIGIT_COUNT - PAIR_CODE_LENGTH) as u32;
+
+/// The rows and columns each grid digit splits the area into
+const GRID_ROWS: i64 = 5;
+const GRID_COLUMNS: i64 = 4;
+
+/// The value of the first pair of digits in units of the last pair
+const PAIR_FIRST_PLACE_VALUE: i64 = ENCODING_BASE.pow(4);
+
+/// The number of units of the last pair in a degree
+const PAIR_PRECISION: i64 = ENCODING_BASE.pow(3);
+
+/// The number of units of the last grid digit in a degree of latitude and longitude
+const FINAL_LAT_PRECISION: i64 = PAIR_PRECISION * GRID_ROWS.pow(GRID_CODE_LENGTH);
+const FINAL_LNG_PRECISION: i64 = PAIR_PRECISION * GRID_COLUMNS.pow(GRID_CODE_LENGTH);
+
+const LATITUDE_MAX: i64 = 90;
+const LONGITUDE_MAX: i64 = 180;
+
+/// The reasons a plus code cannot be created or read
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlusCodeError {
+    /// Codes have 2, 4, 6, 8 or more than 8 digits
+    InvalidLength(usize),
+    /// The character is not one of the digits of a plus code
+    InvalidCharacter(char),
+    /// There must be one separator, after an even number of digits and at most 8
+    MisplacedSeparator,
+    /// Padding must be an even number of zeros directly before the separator
+    InvalidPadding,
+    /// The code is shortened relative to a nearby place, so it does not give a location on its own
+    NotFull,
+    /// The first digits give a location past the poles or the antimeridian
+    OutOfRange,
+}
+
+impl Display for PlusCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlusCodeError::InvalidLength(length) => {
+                write!(f, "A plus code cannot have {} digits", length)
+            }
+            PlusCodeError::InvalidCharacter(character) => {
+                write!(f, "{} is not a plus code digit", character)
+            }
+            PlusCodeError::MisplacedSeparator => write!(f, "The separator is misplaced"),
+            PlusCodeError::InvalidPadding => write!(f, "The padding is invalid"),
+            PlusCodeError::NotFull => write!(f, "The plus code is not a full code"),
+            PlusCodeError::OutOfRange => write!(f, "The plus code is out of range"),
+        }
+    }
+}
+
+/// The area a plus code covers, in decimal degrees where north and east are positive
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CodeArea {
+    south: f64,
+    west: f64,
+    north: f64,
+    east: f64,
+    code_length: usize,
+}
+
+impl CodeArea {
+    /// Get the latitude of the southern edge
+    pub fn south(&self) -> f64 {
+        self.south
+    }
+
+    /// Get the longitude of the western edge
+    pub fn west(&self) -> f64 {
+        self.west
+    }
+
+    /// Get the latitude of the northern edge
+    pub fn north(&self) -> f64 {
+        self.north
+    }
+
+    /// Get the longitude of the eastern edge
+    pub fn east(&self) -> f64 {
+        self.east
+    }
+
+    /// Get the number of digits of the code, which sets the size of the area
+    pub fn code_length(&self) -> usize {
+        self.code_length
+    }
+
+    /// Get the latitude of the centre, which never passes the north pole
+    pub fn center_latitude(&self) -> f64 {
+        (self.south + (self.north - self.south) / 2.0).min(LATITUDE_MAX as f64)
+    }
+
+    /// Get the longitude of the centre, which never passes the antimeridian
+    pub fn center_longitude(&self) -> f64 {
+        (self.west + (self.east - self.west) / 2.0).min(LONGITUDE_MAX as f64)
+    }
+}
+
+/// A full Open Location Code, such as 868M662M+28, which names an area on the Earth
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PlusCode {
+    code: String,
+}
+
+impl PlusCode {
+    /// The length of a code that is precise to about 14 metres
+    pub const DEFAULT_LENGTH: usize = PAIR_CODE_LENGTH;
+
+    /// Encode the location as a code with the number of digits provided,
+    /// any digits past 15 are not added
+    pub fn encode(
+        latitude: f64,
+        longitude: f64,
+        code_length: usize,
+    ) -> Result<Self, PlusCodeError> {
+        if code_length < 2 || (code_length < PAIR_CODE_LENGTH && code_length % 2 == 1) {
+            return Err(PlusCodeError::InvalidLength(code_length));
+        }
+
+        let code_length = code_length.min(MAX_DIGIT_COUNT);
+
+        let (mut latitude, mut longitude) = Self::location_to_integers(latitude, longitude);
+
+        // Work out the grid digits from the last to the first, or skip past their precision
+        let mut digits = Vec::with_capacity(MAX_DIGIT_COUNT);
+        if code_length > PAIR_CODE_LENGTH {
+            for _ in 0..GRID_CODE_LENGTH {
+                let row = latitude % GRID_ROWS;
+                let column = longitude % GRID_COLUMNS;
+
+                digits.push(ALPHABET[(row * GRID_COLUMNS + column) as usize]);
+
+                latitude /= GRID_ROWS;
+                longitude /= GRID_COLUMNS;
+            }
+        } else {
+            latitude /= GRID_ROWS.pow(GRID_CODE_LENGTH);
+            longitude /= GRID_COLUMNS.pow(GRID_CODE_LENGTH);
+        }
+
+        // Then the pairs from the last to the first, longitude comes after latitude
+        for _ in 0..PAIR_CODE_LENGTH / 2 {
+            digits.push(ALPHABET[(longitude % ENCODING_BASE) as usize]);
+            digits.push(ALPHABET[(latitude % ENCODING_BASE) as usize]);
+
+            latitude /= ENCODING_BASE;
+            longitude /= ENCODING_BASE;
+        }
+
+        digits.reverse();
+        digits.truncate(code_length);
+
+        let mut code: String = digits.into_iter().map(char::from).collect();
+
+        // Short codes are padded up to the separator
+        if code_length < SEPARATOR_POSITION {
+            code.extend(std::iter::repeat_n(
+                PADDING,
+                SEPARATOR_POSITION - code_length,
+            ));
+        }
+
+        code.insert(SEPARATOR_POSITION, SEPARATOR);
+
+        Ok(Self { code })
+    }
+
+    /// Convert the location into whole units of the last grid digit from the south pole and the antimeridian,
+    /// the latitude is clipped to the poles and the longitude is wrapped around the Earth
+    fn location_to_integers(latitude: f64, longitude: f64) -> (i64, i64) {
+        // Round away floating point error before taking the floor, so 0.3 does not fall into the cell below
+        let to_units = |degrees: f64, precision: i64| {
+            ((degrees * precision as f64 * 1e6).round() / 1e6).floor() as i64
+        };
+
+        let latitude = (to_units(latitude, FINAL_LAT_PRECISION)
+            + LATITUDE_MAX * FINAL_LAT_PRECISION)
+            .clamp(0, 2 * LATITUDE_MAX * FINAL_LAT_PRECISION - 1);
+
+        let longitude = (to_units(longitude, FINAL_LNG_PRECISION)
+            + LONGITUDE_MAX * FINAL_LNG_PRECISION)
+            .rem_euclid(2 * LONGITUDE_MAX * FINAL_LNG_PRECISION);
+
+        (latitude, longitude)
+    }
+
+    /// Get the code as text, such as 868M662M+28
+    pub fn as_str(&self) -> &str {
+        &self.code
+    }
+
+    /// Get the digits of the code, without the separator or padding
+    fn digits(&self) -> impl Iterator<Item = u8> + '_ {
+        self.code
+            .bytes()
+            .filter(|&digit| digit != SEPARATOR as u8 && digit != PADDING as u8)
+            .take(MAX_DIGIT_COUNT)
+    }
+
+    /// Get the number of digits of the code that are used
+    pub fn code_length(&self) -> usize {
+        self.digits().count()
+    }
+
+    /// Find the area the code covers
+    pub fn decode(&self) -> CodeArea {
+        let values: Vec<i64> = self.digits().map(Self::digit_value).collect();
+
+        let mut pair_latitude = -LATITUDE_MAX * PAIR_PRECISION;
+        let mut pair_longitude = -LONGITUDE_MAX * PAIR_PRECISION;
+
+        // Add up the pairs, the place value ends as the size of the area of the last pair
+        let pair_digits = values.len().min(PAIR_CODE_LENGTH);
+        let mut place_value = PAIR_FIRST_PLACE_VALUE;
+        for (index, pair) in values[..pair_digits].chunks(2).enumerate() {
+            pair_latitude += pair[0] * place_value;
+            pair_longitude += pair[1] * place_value;
+
+            if 2 * index + 2 < pair_digits {
+                place_value /= ENCODING_BASE;
+            }
+        }
+
+        let mut latitude_size = place_value as f64 / PAIR_PRECISION as f64;
+        let mut longitude_size = place_value as f64 / PAIR_PRECISION as f64;
+
+        // Then each grid digit picks a row and a column of the area of the last pair
+        let mut grid_latitude = 0;
+        let mut grid_longitude = 0;
+        if values.len() > PAIR_CODE_LENGTH {
+            let grid_values = &values[PAIR_CODE_LENGTH..];
+
+            let mut row_value = GRID_ROWS.pow(GRID_CODE_LENGTH - 1);
+            let mut column_value = GRID_COLUMNS.pow(GRID_CODE_LENGTH - 1);
+            for (index, value) in grid_values.iter().enumerate() {
+                grid_latitude += (value / GRID_COLUMNS) * row_value;
+                grid_longitude += (value % GRID_COLUMNS) * column_value;
+
+                if index + 1 < grid_values.len() {
+                    row_value /= GRID_ROWS;
+                    column_value /= GRID_COLUMNS;
+                }
+            }
+
+            latitude_size = row_value as f64 / FINAL_LAT_PRECISION as f64;
+            longitude_size = column_value as f64 / FINAL_LNG_PRECISION as f64;
+        }
+
+        let south = pair_latitude as f64 / PAIR_PRECISION as f64
+            + grid_latitude as f64 / FINAL_LAT_PRECISION as f64;
+        let west = pair_longitude as f64 / PAIR_PRECISION as f64
+            + grid_longitude as f64 / FINAL_LNG_PRECISION as f64;
+
+        CodeArea {
+            south,
+            west,
+            north: south + latitude_size,
+            east: west + longitude_size,
+            code_length: values.len(),
+        }
+    }
+
+    /// Get the value of a digit, which must be in the alphabet
+    fn digit_value(digit: u8) -> i64 {
+        ALPHABET
+            .iter()
+            .position(|&letter| letter == digit)
+            .expect("A plus code should only contain digits from the alphabet") as i64
+    }
+}
+
+impl FromStr for PlusCode {
+    type Err = PlusCodeError;
+
+    /// Read a full code, ignoring case
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let code = code.to_ascii_uppercase();
+
+        if let Some(character) = code.chars().find(|&character| {
+            character != SEPARATOR
+                && character != PADDING
+                && !(character.is_ascii() && ALPHABET.contains(&(character as u8)))
+        }) {
+            return Err(PlusCodeError::InvalidCharacter(character));
+        }
+
+        // There must be a single separator after an even number of digits
+        let separator = code
+            .find(SEPARATOR)
+            .ok_or(PlusCodeError::MisplacedSeparator)?;
+        if code.matches(SEPARATOR).count() != 1
+            || separator > SEPARATOR_POSITION
+            || separator % 2 == 1
+        {
+            return Err(PlusCodeError::MisplacedSeparator);
+        }
+
+        // A single digit after the separator is not allowed
+        if code.len() - separator - 1 == 1 {
+            return Err(PlusCodeError::InvalidLength(SEPARATOR_POSITION + 1));
+        }
+
+        // Padding must be an even run of zeros that ends at the separator, with nothing after it
+        if let Some(padding) = code.find(PADDING) {
+            if padding == 0
+                || padding % 2 == 1
+                || padding > separator
+                || code[padding..separator]
+                    .bytes()
+                    .any(|digit| digit != PADDING as u8)
+                || !code.ends_with(SEPARATOR)
+            {
+                return Err(PlusCodeError::InvalidPadding);
+            }
+        }
+
+        if separator < SEPARATOR_POSITION {
+            return Err(PlusCodeError::NotFull);
+        }
+
+        // The first pair must not be past the north pole or the antimeridian
+        let bytes = code.as_bytes();
+        if Self::digit_value(bytes[0]) * ENCODING_BASE >= 2 * LATITUDE_MAX
+            || Self::digit_value(bytes[1]) * ENCODING_BASE >= 2 * LONGITUDE_MAX
+        {
+            return Err(PlusCodeError::OutOfRange);
+        }
+
+        Ok(Self { code })
+    }
+}
+
+impl Display for PlusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlusCode, PlusCodeError};
+
+    #[test]
+    fn test_encode() {
+        // Latitude, longitude, length and code from the Open Location Code test data
+        let vectors = [
+            (20.375, 2.775, 6, "7FG49Q00+"),
+            (20.3700625, 2.7821875, 10, "7FG49QCJ+2V"),
+            (20.3701125, 2.782234375, 11, "7FG49QCJ+2VX"),
+            (20.3701135, 2.78223535156, 13, "7FG49QCJ+2VXGJ"),
+            (47.0000625, 8.0000625, 10, "8FVC2222+22"),
+            (-41.2730625, 174.7859375, 10, "4VCPPQGP+Q9"),
+            (0.5, -179.5, 4, "62G20000+"),
+            (-89.5, -179.5, 4, "22220000+"),
+            (20.5, 2.5, 4, "7FG40000+"),
+            (-89.9999375, -179.9999375, 10, "22222222+22"),
+            (0.5, 179.5, 4, "6VGX0000+"),
+            (1.0, 1.0, 11, "6FH32222+222"),
+            (90.0, 1.0, 4, "CFX30000+"),
+            (92.0, 1.0, 4, "CFX30000+"),
+            (1.0, 180.0, 4, "62H20000+"),
+            (1.0, 181.0, 4, "62H30000+"),
+        ];
+
+        for (latitude, longitude, length, code) in vectors {
+            assert_eq!(
+                PlusCode::encode(latitude, longitude, length)
+                    .unwrap()
+                    .as_str(),
+                code,
+                "Encoding {}, {}",
+                latitude,
+                longitude
+            );
+        }
+
+        assert_eq!(
+            PlusCode::encode(1.0, 1.0, 7),
+            Err(PlusCodeError::InvalidLength(7))
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        // Code, length and the south west and north east corners from the Open Location Code test data
+        let vectors = [
+            ("7FG49Q00+", 6, [20.35, 2.75, 20.4, 2.8]),
+            ("7FG49QCJ+2V", 10, [20.37, 2.782125, 20.370125, 2.78225]),
+            (
+                "7FG49QCJ+2VX",
+                11,
+                [20.3701, 2.78221875, 20.370125, 2.78225],
+            ),
+            (
+                "7FG49QCJ+2VXGJ",
+                13,
+                [20.370113, 2.782234375, 20.370114, 2.78223632813],
+            ),
+            ("8FVC2222+22", 10, [47.0, 8.0, 47.000125, 8.000125]),
+            (
+                "4VCPPQGP+Q9",
+                10,
+                [-41.273125, 174.785875, -41.273, 174.786],
+            ),
+            ("62G20000+", 4, [0.0, -180.0, 1.0, -179.0]),
+            ("22220000+", 4, [-90.0, -180.0, -89.0, -179.0]),
+            ("7FG40000+", 4, [20.0, 2.0, 21.0, 3.0]),
+            ("22222222+22", 10, [-90.0, -180.0, -89.999875, -179.999875]),
+            ("6VGX0000+", 4, [0.0, 179.0, 1.0, 180.0]),
+        ];
+
+        for (code, length, [south, west, north, east]) in vectors {
+            let area = code.parse::<PlusCode>().unwrap().decode();
+
+            assert_eq!(area.code_length(), length);
+            for (found, expected) in [
+                (area.south(), south),
+                (area.west(), west),
+                (area.north(), north),
+                (area.east(), east),
+            ] {
+                assert!(
+                    (found - expected).abs() < 1e-10,
+                    "Decoding {}, found {} expected {}",
+                    code,
+                    found,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!("8fwc2345+g6".parse::<PlusCode>().is_ok());
+        assert!("8FWC2345+G6G".parse::<PlusCode>().is_ok());
+        assert!("8FWC2300+".parse::<PlusCode>().is_ok());
+
+        assert_eq!("WC2345+G6".parse::<PlusCode>(), Err(PlusCodeError::NotFull));
+        assert_eq!(
+            "8FWC2345G6".parse::<PlusCode>(),
+            Err(PlusCodeError::MisplacedSeparator)
+        );
+        assert_eq!(
+            "8FWC2_45+G6".parse::<PlusCode>(),
+            Err(PlusCodeError::InvalidCharacter('_'))
+        );
+        assert_eq!(
+            "8FWC2300+G6".parse::<PlusCode>(),
+            Err(PlusCodeError::InvalidPadding)
+        );
+        assert_eq!(
+            "8FWC2345+G".parse::<PlusCode>(),
+            Err(PlusCodeError::InvalidLength(9))
+        );
+        assert_eq!(
+            "X2222222+22".parse::<PlusCode>(),
+            Err(PlusCodeError::OutOfRange)
+        );
+    }
+}
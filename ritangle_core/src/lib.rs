@@ -0,0 +1,6 @@
+pub mod digits;
+pub mod exact;
+pub mod geography;
+pub mod lookup_tables;
+pub mod puzzle;
+pub mod scan;
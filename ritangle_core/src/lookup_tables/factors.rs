@@ -0,0 +1,301 @@
+use std::{iter, sync::LazyLock};
+
+use tinyvec::ArrayVec;
+
+/// The prime powers of a number, a usize has at most 15 distinct prime factors
+pub type Factorisation = ArrayVec<[PrimePower; 15]>;
+
+/// How the sum of the proper divisors of a number compares to the number itself
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivisorClass {
+    /// The proper divisors sum to less than the number
+    Deficient,
+    /// The proper divisors sum to exactly the number
+    Perfect,
+    /// The proper divisors sum to more than the number
+    Abundant,
+}
+
+/// A prime factor and the number of times it divides a number
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrimePower {
+    pub prime: usize,
+    pub exponent: u32,
+}
+
+/// A table of the smallest prime factor of every number, used to factorise numbers quickly
+pub struct FactorTable;
+
+impl FactorTable {
+    /// The table can factorise every number below this limit
+    pub const LIMIT: usize = 10_000;
+
+    /// Get the smallest prime factor of every number below the limit, 0 and 1 map to themselves
+    fn smallest_prime_factors() -> &'static [usize] {
+        static SMALLEST_PRIME_FACTORS: LazyLock<Vec<usize>> = LazyLock::new(|| {
+            // Start with every number as its own smallest factor, which holds for 0, 1 and the primes
+            let mut factors: Vec<usize> = (0..FactorTable::LIMIT).collect();
+
+            // Sieve out the multiples of every prime, only the first prime to reach a number is kept
+            for number in 2..FactorTable::LIMIT {
+                if number * number >= FactorTable::LIMIT {
+                    // Every composite number left has already been marked by a smaller prime
+                    break;
+                }
+
+                if factors[number] != number {
+                    // This is composite so its multiples are marked by its own factors
+                    continue;
+                }
+
+                for multiple in (number * number..FactorTable::LIMIT).step_by(number) {
+                    if factors[multiple] == multiple {
+                        factors[multiple] = number;
+                    }
+                }
+            }
+
+            factors
+        });
+
+        &SMALLEST_PRIME_FACTORS
+    }
+
+    /// Get the smallest prime that divides the number, returns None for 0 and 1.
+    /// Numbers beyond the table are checked by trial division, which is slower
+    pub fn smallest_prime_factor(number: usize) -> Option<usize> {
+        if number < 2 {
+            return None;
+        }
+
+        if number < Self::LIMIT {
+            return Some(Self::smallest_prime_factors()[number]);
+        }
+
+        // The number is too large for the table so check 2 and then every odd divisor instead
+        let divisor = iter::once(2)
+            .chain((3..=number.isqrt()).step_by(2))
+            .find(|&divisor| number.is_multiple_of(divisor));
+
+        Some(divisor.unwrap_or(number))
+    }
+
+    /// Split the number into its prime powers, ordered from the smallest prime to the largest.
+    /// Returns None for 0, which has no prime factorisation
+    pub fn factorise(number: usize) -> Option<Factorisation> {
+        if number == 0 {
+            return None;
+        }
+
+        let mut factorisation = Factorisation::new();
+        let mut remaining = number;
+
+        // Keep dividing by the smallest prime factor until nothing is left
+        while let Some(prime) = Self::smallest_prime_factor(remaining) {
+            remaining /= prime;
+
+            // Either count this prime again or start a new power
+            match factorisation.last_mut() {
+                Some(power) if power.prime == prime => power.exponent += 1,
+                _ => factorisation.push(PrimePower { prime, exponent: 1 }),
+            }
+        }
+
+        Some(factorisation)
+    }
+
+    /// Get every divisor of the number in ascending order, including 1 and the number itself.
+    /// Every number divides 0, so it returns None for 0
+    pub fn divisors(number: usize) -> Option<Vec<usize>> {
+        let mut divisors = vec![1];
+
+        // Multiply every divisor found so far by each power of the next prime
+        for power in Self::factorise(number)? {
+            let existing = divisors.len();
+            let mut multiplier = 1;
+
+            for _ in 0..power.exponent {
+                multiplier *= power.prime;
+
+                for i in 0..existing {
+                    divisors.push(divisors[i] * multiplier);
+                }
+            }
+        }
+
+        divisors.sort_unstable();
+
+        Some(divisors)
+    }
+
+    /// Count how many divisors the number has, including 1 and the number itself, returns None for 0
+    pub fn divisor_count(number: usize) -> Option<usize> {
+        let count = Self::factorise(number)?
+            .into_iter()
+            .map(|power| power.exponent as usize + 1)
+            .product();
+
+        Some(count)
+    }
+
+    /// Sum every divisor of the number, including the number itself, also known as σ(n).
+    /// Returns None for 0 and when the sum is too large for a usize
+    pub fn divisor_sum(number: usize) -> Option<usize> {
+        Self::factorise(number)?
+            .into_iter()
+            .try_fold(1usize, |sum, power| {
+                // 1 + p + p² + ... + p^k for each prime power p^k
+                let mut term = 1usize;
+                let mut power_sum = 1usize;
+                for _ in 0..power.exponent {
+                    term = term.checked_mul(power.prime)?;
+                    power_sum = power_sum.checked_add(term)?;
+                }
+
+                sum.checked_mul(power_sum)
+            })
+    }
+
+    /// Sum every divisor of the number except the number itself, returns None for 0
+    pub fn proper_divisor_sum(number: usize) -> Option<usize> {
+        Some(Self::divisor_sum(number)? - number)
+    }
+
+    /// Count the numbers from 1 to the number that are coprime to it, also known as φ(n), returns None for 0
+    pub fn totient(number: usize) -> Option<usize> {
+        let mut totient = number;
+
+        // φ(n) = n multiplied by (1 - 1/p) for every distinct prime p
+        for power in Self::factorise(number)? {
+            totient = totient / power.prime * (power.prime - 1);
+        }
+
+        Some(totient)
+    }
+
+    /// Get the product of the distinct primes that divide the number, returns None for 0
+    pub fn radical(number: usize) -> Option<usize> {
+        let radical = Self::factorise(number)?
+            .into_iter()
+            .map(|power| power.prime)
+            .product();
+
+        Some(radical)
+    }
+
+    /// Classify the number by comparing it with the sum of its proper divisors.
+    /// Returns None for 0 and when the sum is too large for a usize
+    pub fn classify(number: usize) -> Option<DivisorClass> {
+        let proper_sum = Self::proper_divisor_sum(number)?;
+
+        let class = match proper_sum.cmp(&number) {
+            std::cmp::Ordering::Less => DivisorClass::Deficient,
+            std::cmp::Ordering::Equal => DivisorClass::Perfect,
+            std::cmp::Ordering::Greater => DivisorClass::Abundant,
+        };
+
+        Some(class)
+    }
+
+    /// Check if the number is the product of exactly two primes, which may be the same prime
+    pub fn is_semiprime(number: usize) -> bool {
+        let prime_count: u32 = Self::factorise(number)
+            .into_iter()
+            .flatten()
+            .map(|power| power.exponent)
+            .sum();
+
+        prime_count == 2
+    }
+
+    /// Check if the number is the product of two different primes
+    pub fn is_distinct_semiprime(number: usize) -> bool {
+        Self::factorise(number).is_some_and(|factorisation| {
+            factorisation.len() == 2 && factorisation.iter().all(|power| power.exponent == 1)
+        })
+    }
+
+    /// Check if no square number other than 1 divides the number, which is false for 0 as every square divides it
+    pub fn is_squarefree(number: usize) -> bool {
+        Self::factorise(number)
+            .is_some_and(|factorisation| factorisation.iter().all(|power| power.exponent == 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DivisorClass, FactorTable, PrimePower};
+
+    #[test]
+    fn test_factorise() {
+        assert!(FactorTable::factorise(1).unwrap().is_empty());
+        assert_eq!(FactorTable::factorise(0), None);
+
+        assert_eq!(
+            FactorTable::factorise(360).unwrap().as_slice(),
+            [
+                PrimePower {
+                    prime: 2,
+                    exponent: 3
+                },
+                PrimePower {
+                    prime: 3,
+                    exponent: 2
+                },
+                PrimePower {
+                    prime: 5,
+                    exponent: 1
+                },
+            ]
+        );
+
+        assert_eq!(FactorTable::smallest_prime_factor(997), Some(997));
+        assert_eq!(FactorTable::smallest_prime_factor(9_991), Some(97));
+
+        // Numbers beyond the table are factorised by trial division
+        assert_eq!(FactorTable::smallest_prime_factor(10_007), Some(10_007));
+        assert_eq!(FactorTable::smallest_prime_factor(10_403), Some(101));
+        assert_eq!(FactorTable::factorise(10_000).unwrap().len(), 2);
+
+        // The product of the first 15 primes
+        let primorial = 614_889_782_588_491_410;
+        assert_eq!(FactorTable::factorise(primorial).unwrap().len(), 15);
+    }
+
+    #[test]
+    fn test_divisor_functions() {
+        assert_eq!(FactorTable::divisors(28), Some(vec![1, 2, 4, 7, 14, 28]));
+        assert_eq!(FactorTable::divisor_count(360), Some(24));
+        assert_eq!(FactorTable::divisor_sum(12), Some(28));
+        assert_eq!(FactorTable::proper_divisor_sum(220), Some(284));
+        assert_eq!(FactorTable::totient(36), Some(12));
+        assert_eq!(FactorTable::totient(1), Some(1));
+        assert_eq!(FactorTable::radical(72), Some(6));
+
+        // Five digit numbers are beyond the table
+        assert_eq!(FactorTable::divisor_count(10_000), Some(25));
+        assert_eq!(FactorTable::divisor_sum(12_345), Some(19_776));
+
+        // Zero has no factorisation, and a sum too large for a usize is not given
+        assert_eq!(FactorTable::divisor_count(0), None);
+        assert_eq!(FactorTable::divisors(0), None);
+        assert_eq!(FactorTable::divisor_sum(usize::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_classification() {
+        assert_eq!(FactorTable::classify(496), Some(DivisorClass::Perfect));
+        assert_eq!(FactorTable::classify(12), Some(DivisorClass::Abundant));
+        assert_eq!(FactorTable::classify(13), Some(DivisorClass::Deficient));
+        assert_eq!(FactorTable::classify(0), None);
+
+        assert!(FactorTable::is_semiprime(49));
+        assert!(!FactorTable::is_distinct_semiprime(49));
+        assert!(FactorTable::is_distinct_semiprime(35));
+        assert!(!FactorTable::is_semiprime(30));
+
+        assert!(FactorTable::is_squarefree(30));
+        assert!(!FactorTable::is_squarefree(18));
+        assert!(!FactorTable::is_squarefree(0) && !FactorTable::is_semiprime(0));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod coprimes;
+pub mod factors;
 pub mod primes;
 pub mod pythagorean_triples;
+pub mod sequences;
 pub mod square;
 pub mod triangular;
@@ -0,0 +1,383 @@
+use std::{
+    fmt::{self, Display},
+    iter,
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+use crate::{
+    digits::{digit_sum, digits_reversed},
+    lookup_tables::factors::FactorTable,
+};
+
+/// A named sequence of positive whole numbers that a clue can refer to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sequence {
+    Prime,
+    Square,
+    Cube,
+    Triangular,
+    Pentagonal,
+    Hexagonal,
+    Fibonacci,
+    Factorial,
+    PowerOfTwo,
+    /// Every digit is the same, such as 7 or 444
+    Repdigit,
+    /// Divisible by the sum of its digits
+    Harshad,
+    /// The square can be split into two parts that add back up to the number, such as 45² = 20|25
+    Kaprekar,
+}
+
+/// The error returned when a name does not match any sequence
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownSequence(pub String);
+
+impl Display for UnknownSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "There is no sequence called \"{}\"", self.0)
+    }
+}
+
+impl Sequence {
+    /// Get the name used to refer to this sequence
+    pub fn name(&self) -> &'static str {
+        match self {
+            Sequence::Prime => "prime",
+            Sequence::Square => "square",
+            Sequence::Cube => "cube",
+            Sequence::Triangular => "triangular",
+            Sequence::Pentagonal => "pentagonal",
+            Sequence::Hexagonal => "hexagonal",
+            Sequence::Fibonacci => "fibonacci",
+            Sequence::Factorial => "factorial",
+            Sequence::PowerOfTwo => "power_of_two",
+            Sequence::Repdigit => "repdigit",
+            Sequence::Harshad => "harshad",
+            Sequence::Kaprekar => "kaprekar",
+        }
+    }
+
+    /// Check if the number is a member of this sequence
+    pub fn contains(&self, number: usize) -> bool {
+        if number == 0 {
+            // None of the sequences start at 0
+            return false;
+        }
+
+        match self {
+            Sequence::Prime => is_prime(number),
+            Sequence::Square => is_square(number),
+            Sequence::Cube => {
+                // Round the floating point root and let the integer check correct any error
+                let root = (number as f64).cbrt().round() as usize;
+
+                root.checked_pow(3) == Some(number)
+            }
+            // n = k(k + 1) / 2 exactly when 8n + 1 is a square
+            Sequence::Triangular => scaled_root(number, 8).is_some(),
+            // n = k(3k - 1) / 2 exactly when 24n + 1 is a square whose root is 5 mod 6
+            Sequence::Pentagonal => scaled_root(number, 24).is_some_and(|root| root % 6 == 5),
+            // n = k(2k - 1) exactly when 8n + 1 is a square whose root is 3 mod 4
+            Sequence::Hexagonal => scaled_root(number, 8).is_some_and(|root| root % 4 == 3),
+            Sequence::Fibonacci => fibonacci_terms()
+                .find(|&term| term >= number)
+                .is_some_and(|term| term == number),
+            Sequence::Factorial => factorial_terms()
+                .find(|&term| term >= number)
+                .is_some_and(|term| term == number),
+            Sequence::PowerOfTwo => number.is_power_of_two(),
+            Sequence::Repdigit => {
+                let last_digit = number % 10;
+
+                digits_reversed(number).all(|digit| digit == last_digit)
+            }
+            Sequence::Harshad => number.is_multiple_of(digit_sum(number)),
+            Sequence::Kaprekar => {
+                // The square of a number this large does not fit, so it cannot be checked
+                let Some(square) = number.checked_mul(number) else {
+                    return false;
+                };
+
+                // Try every split of the square, the right hand part must not be zero
+                let mut divisor: usize = 10;
+                loop {
+                    let (left, right) = (square / divisor, square % divisor);
+
+                    if right != 0 && left + right == number {
+                        return true;
+                    }
+
+                    match divisor.checked_mul(10) {
+                        // Every split has been tried
+                        Some(next) if divisor <= square => divisor = next,
+                        _ => return false,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get every member of the sequence whose number of digits is within the range, in ascending order
+    pub fn members(&self, num_digits: RangeInclusive<u32>) -> Vec<usize> {
+        // Convert the number of digits into the smallest and largest number allowed,
+        // where the largest is capped at the largest usize
+        let Some(lower) = 10usize.checked_pow(num_digits.start().saturating_sub(1)) else {
+            return Vec::new();
+        };
+        let upper = 10usize
+            .checked_pow(*num_digits.end())
+            .map_or(usize::MAX, |power| power - 1);
+
+        if lower > upper {
+            return Vec::new();
+        }
+
+        match self {
+            Sequence::Square => members_of_formula(lower, upper, |k| k.checked_mul(k)),
+            Sequence::Cube => members_of_formula(lower, upper, |k| k.checked_pow(3)),
+            Sequence::Triangular => {
+                members_of_formula(lower, upper, |k| Some(k.checked_mul(k + 1)? / 2))
+            }
+            Sequence::Pentagonal => members_of_formula(lower, upper, |k| {
+                Some(k.checked_mul(k.checked_mul(3)? - 1)? / 2)
+            }),
+            Sequence::Hexagonal => {
+                members_of_formula(lower, upper, |k| k.checked_mul(k.checked_mul(2)? - 1))
+            }
+            Sequence::PowerOfTwo => {
+                members_of_formula(lower, upper, |k| 1usize.checked_shl(k as u32 - 1))
+            }
+            Sequence::Fibonacci => members_of_terms(lower, upper, fibonacci_terms()),
+            Sequence::Factorial => members_of_terms(lower, upper, factorial_terms()),
+            Sequence::Repdigit => {
+                let mut members = Vec::new();
+
+                for length in num_digits.clone().filter(|&length| length > 0) {
+                    // 111...1 multiplied by each digit, stopping once they no longer fit
+                    let Some(ones) = (1..length)
+                        .try_fold(1usize, |ones, _| ones.checked_mul(10)?.checked_add(1))
+                    else {
+                        break;
+                    };
+
+                    members.extend((1..10).map_while(|digit: usize| digit.checked_mul(ones)));
+                }
+
+                members
+            }
+            // There is no simple formula for these, so test every number in the range
+            Sequence::Prime | Sequence::Harshad | Sequence::Kaprekar => (lower..=upper)
+                .filter(|&number| self.contains(number))
+                .collect(),
+        }
+    }
+}
+
+impl Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Sequence {
+    type Err = UnknownSequence;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        SequenceTable::by_name(name).ok_or_else(|| UnknownSequence(name.to_string()))
+    }
+}
+
+/// A registry of every named sequence
+pub struct SequenceTable;
+
+impl SequenceTable {
+    /// Every sequence that can be referred to by name
+    const SEQUENCES: [Sequence; 12] = [
+        Sequence::Prime,
+        Sequence::Square,
+        Sequence::Cube,
+        Sequence::Triangular,
+        Sequence::Pentagonal,
+        Sequence::Hexagonal,
+        Sequence::Fibonacci,
+        Sequence::Factorial,
+        Sequence::PowerOfTwo,
+        Sequence::Repdigit,
+        Sequence::Harshad,
+        Sequence::Kaprekar,
+    ];
+
+    pub fn sequences() -> &'static [Sequence] {
+        &Self::SEQUENCES
+    }
+
+    /// Look up a sequence by its name, ignoring case and treating spaces and dashes as underscores
+    pub fn by_name(name: &str) -> Option<Sequence> {
+        let normalised: String = name
+            .trim()
+            .chars()
+            .map(|c| match c {
+                ' ' | '-' => '_',
+                c => c.to_ascii_lowercase(),
+            })
+            .collect();
+
+        Self::SEQUENCES
+            .iter()
+            .copied()
+            .find(|sequence| sequence.name() == normalised)
+    }
+
+    /// Get the names of every sequence the number is a member of
+    pub fn sequences_containing(number: usize) -> impl Iterator<Item = Sequence> {
+        Self::SEQUENCES
+            .into_iter()
+            .filter(move |sequence| sequence.contains(number))
+    }
+}
+
+fn is_square(number: usize) -> bool {
+    number.isqrt().pow(2) == number
+}
+
+fn is_prime(number: usize) -> bool {
+    FactorTable::smallest_prime_factor(number) == Some(number)
+}
+
+/// Find the root of scale × n + 1 if it is a square, which is None if it is not or it is too large for a usize
+fn scaled_root(number: usize, scale: usize) -> Option<usize> {
+    let scaled = number.checked_mul(scale)?.checked_add(1)?;
+
+    is_square(scaled).then(|| scaled.isqrt())
+}
+
+/// Get the Fibonacci numbers 1, 2, 3, 5, ... up to the largest that fits in a usize
+fn fibonacci_terms() -> impl Iterator<Item = usize> {
+    // The next term is None once it no longer fits
+    iter::successors(Some((1usize, Some(2usize))), |&(current, next)| {
+        let next = next?;
+
+        Some((next, current.checked_add(next)))
+    })
+    .map(|(current, _)| current)
+}
+
+/// Get the factorials 1, 2, 6, 24, ... up to the largest that fits in a usize
+fn factorial_terms() -> impl Iterator<Item = usize> {
+    iter::successors(Some((1usize, 2usize)), |&(factorial, multiplier)| {
+        Some((factorial.checked_mul(multiplier)?, multiplier + 1))
+    })
+    .map(|(factorial, _)| factorial)
+}
+
+/// Collect the values of an increasing formula, starting from k = 1, that lie between the bounds,
+/// stopping when the formula returns None as the value no longer fits
+fn members_of_formula(
+    lower: usize,
+    upper: usize,
+    formula: impl Fn(usize) -> Option<usize>,
+) -> Vec<usize> {
+    members_of_terms(lower, upper, (1..).map_while(formula))
+}
+
+/// Collect the terms of an increasing sequence that lie between the bounds
+fn members_of_terms(lower: usize, upper: usize, terms: impl Iterator<Item = usize>) -> Vec<usize> {
+    terms
+        .skip_while(|&member| member < lower)
+        .take_while(|&member| member <= upper)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Sequence, SequenceTable};
+
+    #[test]
+    fn test_members() {
+        assert_eq!(Sequence::Cube.members(2..=2), vec![27, 64]);
+        assert_eq!(
+            Sequence::Pentagonal.members(2..=2),
+            vec![12, 22, 35, 51, 70, 92]
+        );
+        assert_eq!(
+            Sequence::Hexagonal.members(1..=2),
+            vec![1, 6, 15, 28, 45, 66, 91]
+        );
+        assert_eq!(Sequence::Fibonacci.members(1..=1), vec![1, 2, 3, 5, 8]);
+        assert_eq!(
+            Sequence::Factorial.members(1..=3),
+            vec![1, 2, 6, 24, 120, 720]
+        );
+        assert_eq!(Sequence::PowerOfTwo.members(3..=3), vec![128, 256, 512]);
+        assert_eq!(
+            Sequence::Kaprekar.members(1..=4),
+            vec![
+                1, 9, 45, 55, 99, 297, 703, 999, 2223, 2728, 4879, 4950, 5050, 5292, 7272, 7777,
+                9999
+            ]
+        );
+        assert_eq!(Sequence::Prime.members(3..=3).len(), 143);
+        assert_eq!(Sequence::Repdigit.members(2..=2)[8], 99);
+    }
+
+    #[test]
+    fn test_members_match_contains() {
+        for sequence in SequenceTable::sequences() {
+            let members = sequence.members(1..=3);
+            let filtered: Vec<usize> = (1..1000).filter(|&n| sequence.contains(n)).collect();
+
+            assert_eq!(members, filtered, "{} members do not match", sequence);
+        }
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        // None of these fit once scaled or squared, so they are not members rather than overflowing
+        for sequence in SequenceTable::sequences() {
+            sequence.contains(usize::MAX);
+        }
+
+        assert!(!Sequence::Triangular.contains(usize::MAX));
+        assert!(!Sequence::Kaprekar.contains(usize::MAX));
+        assert!(Sequence::Fibonacci.contains(12_200_160_415_121_876_738));
+        assert!(Sequence::Factorial.contains(2_432_902_008_176_640_000));
+
+        // The members stop at the largest that fits in a usize
+        assert_eq!(
+            Sequence::Fibonacci.members(20..=25),
+            vec![12_200_160_415_121_876_738]
+        );
+        assert_eq!(
+            Sequence::Factorial.members(19..=30),
+            vec![2_432_902_008_176_640_000]
+        );
+        assert_eq!(
+            Sequence::PowerOfTwo.members(19..=30),
+            vec![1 << 60, 1 << 61, 1 << 62, 1 << 63]
+        );
+        assert_eq!(
+            Sequence::Repdigit.members(20..=21),
+            vec![11_111_111_111_111_111_111]
+        );
+        assert_eq!(Sequence::Square.members(21..=25), vec![]);
+        assert_eq!(Sequence::Cube.members(30..=30), vec![]);
+    }
+
+    #[test]
+    fn test_lookup_by_name() {
+        assert_eq!(
+            SequenceTable::by_name("Power of two"),
+            Some(Sequence::PowerOfTwo)
+        );
+        assert_eq!("harshad".parse(), Ok(Sequence::Harshad));
+        assert!("perfect".parse::<Sequence>().is_err());
+
+        let containing: Vec<Sequence> = SequenceTable::sequences_containing(36).collect();
+        assert_eq!(
+            containing,
+            vec![Sequence::Square, Sequence::Triangular, Sequence::Harshad]
+        );
+    }
+}
@@ -14,8 +14,6 @@ impl SquareTable {
 
             squares.sort_unstable();
 
-            println!("{:?}", squares);
-
             squares
         });
 
@@ -0,0 +1,213 @@
+use hashbrown::HashMap;
+
+use super::{
+    position::{GridPosition, IdentifierVector, PuzzleDirection, PuzzleIdentifier},
+    wall::WallDirection,
+};
+
+/// The shape of a puzzle grid, where the identifiers are and where the walls are located
+#[derive(Debug, PartialEq, Eq)]
+pub struct GridLayout {
+    num_columns: usize,
+    num_rows: usize,
+    identifiers: HashMap<PuzzleIdentifier, GridPosition>,
+    walls: HashMap<GridPosition, WallDirection>,
+    /// The indexes of the cells in every entry of the grid, such as 1 Across
+    entries: HashMap<IdentifierVector, Vec<usize>>,
+}
+
+impl GridLayout {
+    /// Create a new layout of a given size, with its identifiers and the locations of the walls
+    pub fn new(
+        num_columns: usize,
+        num_rows: usize,
+        identifiers: Vec<(PuzzleIdentifier, GridPosition)>,
+        walls: Vec<(GridPosition, WallDirection)>,
+    ) -> Self {
+        let mut layout = Self {
+            num_columns,
+            num_rows,
+            identifiers: identifiers.into_iter().collect(),
+            walls: walls.into_iter().collect(),
+            entries: HashMap::new(),
+        };
+
+        // Find every entry that begins at an identifier
+        let mut entries = HashMap::new();
+        for (&identifier, &position) in &layout.identifiers {
+            for direction in [PuzzleDirection::Across, PuzzleDirection::Down] {
+                if !layout.is_entry_start(position, direction) {
+                    continue;
+                }
+
+                let cells = layout.cells_along(position, direction);
+
+                // A single cell is not an entry
+                if cells.len() > 1 {
+                    entries.insert(IdentifierVector::new(identifier, direction), cells);
+                }
+            }
+        }
+
+        layout.entries = entries;
+
+        layout
+    }
+
+    /// Get the number of columns in the grid
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    /// Get the number of rows in the grid
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Get the number of cells in the grid
+    pub fn num_cells(&self) -> usize {
+        self.num_columns * self.num_rows
+    }
+
+    /// Get all identifiers in the grid
+    pub fn identifiers(&self) -> hashbrown::hash_map::Keys<'_, PuzzleIdentifier, GridPosition> {
+        self.identifiers.keys()
+    }
+
+    /// Lookup the identifier and convert it to a grid position, if it exists
+    pub fn identifier_to_grid(&self, identifier: PuzzleIdentifier) -> Option<GridPosition> {
+        self.identifiers.get(&identifier).copied()
+    }
+
+    /// Convert grid position to index, starting at the top left and reading across each row
+    pub fn position_to_index(&self, position: GridPosition) -> usize {
+        assert!(position.column < self.num_columns);
+        assert!(position.row < self.num_rows);
+
+        position.column + (position.row * self.num_columns)
+    }
+
+    /// Check if the position ahead of us in our direction is blocked
+    pub fn is_direction_blocked(&self, position: GridPosition, direction: PuzzleDirection) -> bool {
+        // Check if there is a wall ahead of us
+        if let Some(&wall) = self.walls.get(&position) {
+            if wall.is_blocked(direction) {
+                return true;
+            }
+        }
+
+        // Check if we have reached the edge
+        let new_position = match direction {
+            PuzzleDirection::Across => position + GridPosition::new(1, 0),
+            PuzzleDirection::Down => position + GridPosition::new(0, 1),
+        };
+
+        new_position.column >= self.num_columns || new_position.row >= self.num_rows
+    }
+
+    /// Check if an entry in our direction can begin at the position,
+    /// which is when the position behind us is either the edge or blocked
+    fn is_entry_start(&self, position: GridPosition, direction: PuzzleDirection) -> bool {
+        let previous = match direction {
+            PuzzleDirection::Across if position.column > 0 => {
+                GridPosition::new(position.column - 1, position.row)
+            }
+            PuzzleDirection::Down if position.row > 0 => {
+                GridPosition::new(position.column, position.row - 1)
+            }
+            _ => return true,
+        };
+
+        self.is_direction_blocked(previous, direction)
+    }
+
+    /// Get the indexes of the cells from the position until we hit a wall or the edge
+    fn cells_along(&self, mut position: GridPosition, direction: PuzzleDirection) -> Vec<usize> {
+        let mut cells = vec![self.position_to_index(position)];
+
+        while !self.is_direction_blocked(position, direction) {
+            // Increment row or column dependent on what direction we are travelling in
+            match direction {
+                PuzzleDirection::Across => position.column += 1,
+                PuzzleDirection::Down => position.row += 1,
+            }
+
+            cells.push(self.position_to_index(position));
+        }
+
+        cells
+    }
+
+    /// Get the indexes of the cells in the entry, returns None if there is no such entry
+    pub fn entry_cells(&self, vector: IdentifierVector) -> Option<&[usize]> {
+        self.entries.get(&vector).map(Vec::as_slice)
+    }
+
+    /// Get every entry in the grid along with the indexes of its cells
+    pub fn entries(&self) -> impl Iterator<Item = (IdentifierVector, &[usize])> {
+        self.entries
+            .iter()
+            .map(|(&vector, cells)| (vector, cells.as_slice()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GridLayout;
+    use crate::puzzle::{
+        position::{GridPosition, IdentifierVector, PuzzleIdentifier},
+        wall::WallDirection,
+    };
+
+    #[test]
+    fn test_entries() {
+        // The stage one grid, with 1, 3 and 5 across and 1, 2 and 4 down
+        let layout = GridLayout::new(
+            3,
+            3,
+            vec![
+                (PuzzleIdentifier(1), GridPosition::new(0, 0)),
+                (PuzzleIdentifier(2), GridPosition::new(1, 0)),
+                (PuzzleIdentifier(3), GridPosition::new(1, 1)),
+                (PuzzleIdentifier(4), GridPosition::new(2, 1)),
+                (PuzzleIdentifier(5), GridPosition::new(0, 2)),
+            ],
+            vec![
+                (GridPosition::new(2, 0), WallDirection::Down),
+                (GridPosition::new(0, 1), WallDirection::Right),
+                (GridPosition::new(1, 1), WallDirection::Down),
+            ],
+        );
+
+        assert_eq!(layout.entries().count(), 6);
+
+        assert_eq!(
+            layout.entry_cells(IdentifierVector::across(1)),
+            Some(&[0, 1, 2][..])
+        );
+        assert_eq!(
+            layout.entry_cells(IdentifierVector::across(3)),
+            Some(&[4, 5][..])
+        );
+        assert_eq!(
+            layout.entry_cells(IdentifierVector::across(5)),
+            Some(&[6, 7, 8][..])
+        );
+        assert_eq!(
+            layout.entry_cells(IdentifierVector::down(1)),
+            Some(&[0, 3, 6][..])
+        );
+        assert_eq!(
+            layout.entry_cells(IdentifierVector::down(2)),
+            Some(&[1, 4][..])
+        );
+        assert_eq!(
+            layout.entry_cells(IdentifierVector::down(4)),
+            Some(&[5, 8][..])
+        );
+
+        // 2 Across continues on from 1 Across so it is not an entry
+        assert_eq!(layout.entry_cells(IdentifierVector::across(2)), None);
+        assert_eq!(layout.entry_cells(IdentifierVector::down(6)), None);
+    }
+}
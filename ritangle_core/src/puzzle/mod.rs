@@ -3,10 +3,14 @@ use std::{
     sync::Arc,
 };
 
+use layout::GridLayout;
 use position::{GridPosition, IdentifierVector, PuzzleDirection, PuzzleIdentifier};
 use tinyvec::ArrayVec;
 use wall::WallDirection;
 
+use crate::digits::join_digits;
+
+pub mod layout;
 pub mod position;
 pub mod wall;
 
@@ -17,20 +21,11 @@ pub enum FitError {
     DigitsDoNotMatch,
 }
 
-/// The shared information used by all subpuzzles
-#[derive(Debug, PartialEq, Eq)]
-struct SharedPuzzle {
-    num_columns: usize,
-    num_rows: usize,
-    identifiers: hashbrown::HashMap<PuzzleIdentifier, GridPosition>,
-    walls: hashbrown::HashMap<GridPosition, WallDirection>,
-}
-
 /// The puzzle to solve through backtracking
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Puzzle {
     digits: Vec<Option<u8>>,
-    shared: Arc<SharedPuzzle>,
+    layout: Arc<GridLayout>,
 }
 
 impl Puzzle {
@@ -44,43 +39,32 @@ impl Puzzle {
         // Create a unset grid of digits
         let digits = vec![None; num_columns * num_rows];
 
-        // Convert identifiers into hashmap
-        let identifiers = identifiers.into_iter().collect();
-
-        // Convert walls into a hashmap
-        let walls = walls.into_iter().collect();
-
         Self {
             digits,
-            shared: Arc::new(SharedPuzzle {
-                num_columns,
-                num_rows,
-                identifiers,
-                walls,
-            }),
+            layout: Arc::new(GridLayout::new(num_columns, num_rows, identifiers, walls)),
         }
     }
 
+    /// Get the layout of the grid shared by all subpuzzles
+    pub fn layout(&self) -> &GridLayout {
+        &self.layout
+    }
+
     /// Get all puzzle identifiers
     pub fn identifers(&self) -> hashbrown::hash_map::Keys<'_, PuzzleIdentifier, GridPosition> {
-        self.shared.identifiers.keys()
+        self.layout.identifiers()
     }
 
     /// Lookup the identifer and convert it to an actual grid position
     pub fn identifer_to_grid(&self, identifier: PuzzleIdentifier) -> GridPosition {
-        *self
-            .shared
-            .identifiers
-            .get(&identifier)
+        self.layout
+            .identifier_to_grid(identifier)
             .expect("The provided identifier should have been valid")
     }
 
     /// Convert grid position to index
     fn position_to_index(&self, position: GridPosition) -> usize {
-        assert!(position.column < self.shared.num_columns);
-        assert!(position.row < self.shared.num_rows);
-
-        position.column + (position.row * self.shared.num_columns)
+        self.layout.position_to_index(position)
     }
 
     /// Lookup digit at position
@@ -92,28 +76,7 @@ impl Puzzle {
 
     /// Check if the position ahead of us in our direction is blocked
     fn is_direction_blocked(&self, position: GridPosition, direction: PuzzleDirection) -> bool {
-        // Check if there is a wall ahead of us
-        if let Some(&wall) = self.shared.walls.get(&position) {
-            if wall.is_blocked(direction) {
-                return true;
-            }
-        }
-
-        // Check if we have reached the edge
-        let new_position = match direction {
-            PuzzleDirection::Across => position + GridPosition::new(1, 0),
-            PuzzleDirection::Down => position + GridPosition::new(0, 1),
-        };
-
-        if new_position.column >= self.shared.num_columns {
-            return true;
-        }
-
-        if new_position.row >= self.shared.num_rows {
-            return true;
-        }
-
-        return false;
+        self.layout.is_direction_blocked(position, direction)
     }
 
     /// Get the digits at the identifier in the given direction
@@ -153,17 +116,12 @@ impl Puzzle {
         let mut full_digits = ArrayVec::<[u8; 3]>::new();
 
         for digit in digits {
-            if digit.is_none() {
-                // This is not a complete field
-                return None;
-            }
-
-            // Add this digit as it is valid
-            full_digits.push(digit.unwrap());
+            // Add this digit if it is valid, otherwise this is not a complete field
+            full_digits.push(digit?);
         }
 
         // Convert the digits to a number and return
-        Some(Self::digits_to_number(&full_digits))
+        Some(join_digits(&full_digits))
     }
 
     /// Set the digits at the identifier in the given direction,
@@ -241,21 +199,6 @@ impl Puzzle {
         buffer
     }
 
-    /// Convert the provided digits into a number
-    fn digits_to_number(digits: &[u8]) -> usize {
-        let mut multiplier = 10usize.pow(digits.len() as u32 - 1);
-
-        let mut number = 0;
-
-        for digit in digits {
-            number += *digit as usize * multiplier;
-
-            multiplier /= 10;
-        }
-
-        number
-    }
-
     /// Check if a number can fit within a field
     fn can_digits_fit(
         &self,
@@ -334,12 +277,13 @@ impl Display for Puzzle {
         writeln!(
             f,
             "Puzzle {}x{} [",
-            self.shared.num_columns, self.shared.num_rows
+            self.layout.num_columns(),
+            self.layout.num_rows()
         )?;
 
         // Output all the rows
-        for row_index in 0..self.shared.num_rows {
-            for column_index in 0..self.shared.num_columns {
+        for row_index in 0..self.layout.num_rows() {
+            for column_index in 0..self.layout.num_columns() {
                 let current_position = GridPosition::new(column_index, row_index);
 
                 let digit_index = self.position_to_index(current_position);
@@ -351,7 +295,7 @@ impl Display for Puzzle {
                 }?;
             }
 
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
 
         write!(f, "]")
@@ -435,16 +379,13 @@ mod tests {
     #[test]
     fn test_digit_conversion() {
         assert_eq!(Puzzle::number_to_digits(789), array_vec!(7, 8, 9));
-
-        assert_eq!(Puzzle::digits_to_number(&[1, 2, 3]), 123);
     }
 
     #[test]
     fn test_digits() {
         let mut puzzle = create_puzzle();
 
-        let test_identifiers =
-            [1, 2, 4, 6, 8, 11, 13, 14].map(|identifier| PuzzleIdentifier(identifier));
+        let test_identifiers = [1, 2, 4, 6, 8, 11, 13, 14].map(PuzzleIdentifier);
 
         let mut i = 100;
         let mut sequence = iter::repeat_with(|| {
@@ -1,7 +1,7 @@
 use std::ops::{Add, AddAssign};
 
 /// A direction on the puzzle grid
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PuzzleDirection {
     /// Represents right on the grid
     Across,
@@ -14,7 +14,7 @@ pub enum PuzzleDirection {
 pub struct PuzzleIdentifier(pub usize);
 
 /// A named vector on the puzzle grid
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct IdentifierVector {
     pub identifier: PuzzleIdentifier,
     pub direction: PuzzleDirection,
@@ -28,6 +28,16 @@ impl IdentifierVector {
             direction,
         }
     }
+
+    /// Create a new vector going across from the identifier, such as 3 Across
+    pub fn across(identifier: usize) -> Self {
+        Self::new(PuzzleIdentifier(identifier), PuzzleDirection::Across)
+    }
+
+    /// Create a new vector going down from the identifier, such as 4 Down
+    pub fn down(identifier: usize) -> Self {
+        Self::new(PuzzleIdentifier(identifier), PuzzleDirection::Down)
+    }
 }
 
 /// Any point on the grid
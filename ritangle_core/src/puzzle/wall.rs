@@ -12,12 +12,12 @@ pub enum WallDirection {
 impl WallDirection {
     /// Check the direction is blocked by this wall
     pub fn is_blocked(&self, direction: PuzzleDirection) -> bool {
-        return match (self, direction) {
+        match (self, direction) {
             (WallDirection::Right, PuzzleDirection::Across) => true,
             (WallDirection::Right, PuzzleDirection::Down) => false,
             (WallDirection::Down, PuzzleDirection::Across) => false,
             (WallDirection::Down, PuzzleDirection::Down) => true,
             (WallDirection::Both, _) => true,
-        };
+        }
     }
 }
@@ -0,0 +1,380 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    ops::Range,
+    path::Path,
+};
+
+/// The first line of every checkpoint file, so that other files are not mistaken for one
+const HEADER: &str = "ritangle scan checkpoint";
+
+/// A value found by a scan that can be written to a line of a checkpoint file and read back
+pub trait ScanRecord: Sized {
+    /// Write the value as text, which must not contain a new line
+    fn to_record(&self) -> String;
+
+    /// Read the value back from the text written by to_record, returns None if the text is not a value
+    fn from_record(record: &str) -> Option<Self>;
+}
+
+/// Scans that only need the indexes they find have nothing more to record
+impl ScanRecord for () {
+    fn to_record(&self) -> String {
+        String::new()
+    }
+
+    fn from_record(record: &str) -> Option<Self> {
+        record.is_empty().then_some(())
+    }
+}
+
+/// Failed to read or write a checkpoint file
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// The file could not be read or written
+    Io(io::Error),
+    /// The line of the file, counting from 1, is not part of a checkpoint
+    InvalidLine(usize),
+    /// The checkpoint was saved by a scan over a different range of indexes
+    DifferentRange {
+        expected: Range<u64>,
+        found: Range<u64>,
+    },
+    /// The checkpoint was saved by a scan looking for something else, such as under other rules
+    DifferentFingerprint { expected: String, found: String },
+}
+
+impl Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(error) => write!(f, "Could not access the checkpoint: {}", error),
+            CheckpointError::InvalidLine(line) => {
+                write!(f, "Line {} of the checkpoint could not be read", line)
+            }
+            CheckpointError::DifferentRange { expected, found } => write!(
+                f,
+                "The checkpoint is for the indexes {:?} rather than {:?}",
+                found, expected
+            ),
+            CheckpointError::DifferentFingerprint { expected, found } => write!(
+                f,
+                "The checkpoint was saved while scanning for \"{}\" rather than \"{}\"",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckpointError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CheckpointError {
+    fn from(error: io::Error) -> Self {
+        CheckpointError::Io(error)
+    }
+}
+
+/// How far a scan over a range of indexes has got, along with the values found so far
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint<T> {
+    range: Range<u64>,
+    /// What the scan is looking for, so that a checkpoint is only resumed by the same kind of scan
+    fingerprint: String,
+    /// The ranges of indexes that have been scanned, in order and with none touching another
+    completed: Vec<Range<u64>>,
+    found: Vec<(u64, T)>,
+}
+
+impl<T> Checkpoint<T> {
+    /// Create a new checkpoint for a scan that has not started
+    pub fn new(range: Range<u64>) -> Self {
+        Self {
+            range,
+            fingerprint: String::new(),
+            completed: Vec::new(),
+            found: Vec::new(),
+        }
+    }
+
+    /// Describe what the scan is looking for on a single line, such as the rules being checked
+    pub fn with_fingerprint(mut self, fingerprint: impl Into<String>) -> Self {
+        let fingerprint = fingerprint.into();
+        assert!(
+            !fingerprint.contains('\n'),
+            "A fingerprint must fit on one line"
+        );
+
+        self.fingerprint = fingerprint;
+        self
+    }
+
+    /// Get the range of indexes the scan covers
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Get the description of what the scan is looking for
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Get the ranges of indexes that have been scanned
+    pub fn completed(&self) -> &[Range<u64>] {
+        &self.completed
+    }
+
+    /// Count how many indexes have been scanned
+    pub fn num_completed(&self) -> u64 {
+        self.completed
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+
+    /// Check if every index of the range has been scanned
+    pub fn is_complete(&self) -> bool {
+        self.num_completed() == self.range.end - self.range.start
+    }
+
+    /// Get the ranges of indexes that are still to be scanned
+    pub fn remaining(&self) -> Vec<Range<u64>> {
+        let mut remaining = Vec::new();
+        let mut start = self.range.start;
+
+        for completed in &self.completed {
+            if completed.start > start {
+                remaining.push(start..completed.start);
+            }
+
+            start = completed.end;
+        }
+
+        if start < self.range.end {
+            remaining.push(start..self.range.end);
+        }
+
+        remaining
+    }
+
+    /// Record that the indexes have been scanned along with the values found within them
+    pub fn complete(&mut self, scanned: Range<u64>, found: Vec<(u64, T)>) {
+        if scanned.is_empty() {
+            return;
+        }
+
+        self.found.extend(found);
+
+        // Insert the range in order, then join it to any ranges it touches
+        let position = self
+            .completed
+            .partition_point(|completed| completed.start < scanned.start);
+        self.completed.insert(position, scanned);
+
+        let mut merged: Vec<Range<u64>> = Vec::with_capacity(self.completed.len());
+        for range in self.completed.drain(..) {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        self.completed = merged;
+    }
+
+    /// Get every value found so far along with its index, in order of the index
+    pub fn into_found(mut self) -> Vec<(u64, T)> {
+        self.found.sort_by_key(|&(index, _)| index);
+
+        self.found
+    }
+}
+
+impl<T: ScanRecord> Checkpoint<T> {
+    /// Load the checkpoint saved by a scan, which must have been over the same range of indexes
+    /// and with the same fingerprint
+    pub fn load(
+        path: impl AsRef<Path>,
+        range: Range<u64>,
+        fingerprint: &str,
+    ) -> Result<Self, CheckpointError> {
+        let checkpoint = Self::from_text(&fs::read_to_string(path)?)?;
+
+        if checkpoint.range != range {
+            return Err(CheckpointError::DifferentRange {
+                expected: range,
+                found: checkpoint.range,
+            });
+        }
+
+        if checkpoint.fingerprint != fingerprint {
+            return Err(CheckpointError::DifferentFingerprint {
+                expected: fingerprint.to_string(),
+                found: checkpoint.fingerprint,
+            });
+        }
+
+        Ok(checkpoint)
+    }
+
+    /// Save the checkpoint, writing to a temporary file first so an interrupted save keeps the last checkpoint
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let path = path.as_ref();
+
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+
+        fs::write(&temporary, self.to_text())?;
+        fs::rename(&temporary, path)?;
+
+        Ok(())
+    }
+
+    /// Write the checkpoint as text, with the range, the fingerprint, each completed range and each value found on its own line
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "{}\nrange {}\nfingerprint {}\n",
+            HEADER,
+            format_range(&self.range),
+            self.fingerprint
+        );
+
+        for completed in &self.completed {
+            text.push_str(&format!("completed {}\n", format_range(completed)));
+        }
+
+        for (index, value) in &self.found {
+            text.push_str(&format!("found {} {}\n", index, value.to_record()));
+        }
+
+        text
+    }
+
+    /// Read a checkpoint from the text written by to_text
+    pub fn from_text(text: &str) -> Result<Self, CheckpointError> {
+        let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        if lines.next().map(|(_, line)| line) != Some(HEADER) {
+            return Err(CheckpointError::InvalidLine(1));
+        }
+
+        let range = lines
+            .next()
+            .and_then(|(_, line)| line.strip_prefix("range "))
+            .and_then(parse_range)
+            .ok_or(CheckpointError::InvalidLine(2))?;
+
+        let fingerprint = lines
+            .next()
+            .and_then(|(_, line)| line.strip_prefix("fingerprint "))
+            .ok_or(CheckpointError::InvalidLine(3))?;
+
+        let mut checkpoint = Self::new(range).with_fingerprint(fingerprint);
+        for (number, line) in lines {
+            let invalid = CheckpointError::InvalidLine(number);
+
+            if let Some(completed) = line.strip_prefix("completed ") {
+                let completed = parse_range(completed)
+                    .filter(|completed| {
+                        checkpoint.range.start <= completed.start
+                            && completed.end <= checkpoint.range.end
+                    })
+                    .ok_or(invalid)?;
+
+                checkpoint.complete(completed, Vec::new());
+            } else if let Some(found) = line.strip_prefix("found ") {
+                // Values with nothing to record have nothing after the index
+                let (index, record) = found.split_once(' ').unwrap_or((found, ""));
+
+                let index = index
+                    .parse()
+                    .map_err(|_| CheckpointError::InvalidLine(number))?;
+                let value = T::from_record(record).ok_or(invalid)?;
+
+                checkpoint.found.push((index, value));
+            } else {
+                return Err(invalid);
+            }
+        }
+
+        Ok(checkpoint)
+    }
+}
+
+/// Write a range of indexes as start..end
+fn format_range(range: &Range<u64>) -> String {
+    format!("{}..{}", range.start, range.end)
+}
+
+/// Read a range of indexes written as start..end
+fn parse_range(text: &str) -> Option<Range<u64>> {
+    let (start, end) = text.split_once("..")?;
+    let range = start.parse().ok()?..end.parse().ok()?;
+
+    (range.start <= range.end).then_some(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Checkpoint, CheckpointError};
+
+    #[test]
+    fn test_complete() {
+        let mut checkpoint = Checkpoint::new(0..100);
+
+        checkpoint.complete(40..60, vec![(45, ())]);
+        checkpoint.complete(0..10, vec![]);
+        checkpoint.complete(10..20, vec![(12, ())]);
+
+        assert_eq!(checkpoint.completed(), [0..20, 40..60]);
+        assert_eq!(checkpoint.remaining(), [20..40, 60..100]);
+        assert_eq!(checkpoint.num_completed(), 40);
+
+        checkpoint.complete(20..40, vec![]);
+        checkpoint.complete(60..100, vec![(99, ())]);
+
+        assert!(checkpoint.is_complete() && checkpoint.remaining().is_empty());
+        assert_eq!(checkpoint.into_found(), [(12, ()), (45, ()), (99, ())]);
+    }
+
+    #[test]
+    fn test_text() {
+        let mut checkpoint = Checkpoint::new(0..100).with_fingerprint("every seventh");
+        checkpoint.complete(0..30, vec![(7, ())]);
+        checkpoint.complete(50..60, vec![(55, ())]);
+
+        let text = checkpoint.to_text();
+
+        assert_eq!(
+            text,
+            "ritangle scan checkpoint\nrange 0..100\nfingerprint every seventh\ncompleted 0..30\ncompleted 50..60\nfound 7 \nfound 55 \n"
+        );
+        assert_eq!(Checkpoint::from_text(&text).unwrap(), checkpoint);
+
+        // A completed range outside of the scan
+        let text = "ritangle scan checkpoint\nrange 0..100\nfingerprint \ncompleted 90..110\n";
+
+        assert!(matches!(
+            Checkpoint::<()>::from_text(text),
+            Err(CheckpointError::InvalidLine(4))
+        ));
+
+        // A checkpoint without a fingerprint
+        assert!(matches!(
+            Checkpoint::<()>::from_text(
+                "ritangle scan checkpoint\nrange 0..100\ncompleted 0..10\n"
+            ),
+            Err(CheckpointError::InvalidLine(3))
+        ));
+        assert!(matches!(
+            Checkpoint::<()>::from_text("range 0..100\n"),
+            Err(CheckpointError::InvalidLine(1))
+        ));
+    }
+}
@@ -0,0 +1,337 @@
+use std::{
+    fs,
+    ops::Range,
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Once,
+    },
+    time::{Duration, Instant},
+};
+
+use checkpoint::{Checkpoint, CheckpointError, ScanRecord};
+use progress::Progress;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+pub mod checkpoint;
+pub mod progress;
+
+/// Set once Ctrl-C has been pressed, which stops every scan
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// The Ctrl-C handler can only be installed once for the whole program
+static INSTALL_INTERRUPT_HANDLER: Once = Once::new();
+
+/// Stop scans at the end of their current chunks when Ctrl-C is pressed, and exit straight away if it is pressed again
+fn install_interrupt_handler() {
+    INSTALL_INTERRUPT_HANDLER.call_once(|| {
+        // The program may have its own handler already, in which case that one is kept
+        let _ = ctrlc::set_handler(|| {
+            if INTERRUPTED.swap(true, Ordering::SeqCst) {
+                process::exit(130);
+            }
+
+            eprintln!("Stopping after the current chunks, press Ctrl-C again to stop now");
+        });
+    });
+}
+
+/// How a scan finished
+#[derive(Debug)]
+pub enum ScanOutcome<T> {
+    /// Every index was scanned, with the values found in order of their index
+    Complete(Vec<(u64, T)>),
+    /// The scan was stopped early, after saving its progress to the checkpoint if it has one
+    Interrupted(Progress),
+}
+
+/// The state shared between the chunks of a running scan
+struct ScanState<T> {
+    checkpoint: Checkpoint<T>,
+    /// The indexes scanned by this run, not counting any resumed from the checkpoint
+    scanned: u64,
+    last_report: Instant,
+    last_save: Instant,
+    error: Option<CheckpointError>,
+}
+
+/// A scan over a range of indexes, such as every possible grid of a puzzle, which are checked in parallel in chunks.
+/// It reports its progress as it goes, stops cleanly on Ctrl-C and can save its progress to a checkpoint to resume from
+pub struct Scan {
+    range: Range<u64>,
+    chunk_size: u64,
+    progress_interval: Option<Duration>,
+    checkpoint: Option<PathBuf>,
+    checkpoint_interval: Duration,
+    fingerprint: String,
+    stop: Arc<AtomicBool>,
+}
+
+impl Scan {
+    /// The number of indexes in each chunk, a scan can only stop or save its progress between chunks
+    pub const DEFAULT_CHUNK_SIZE: u64 = 1 << 20;
+
+    /// Create a new scan over the indexes that reports its progress every 5 seconds
+    pub fn new(range: Range<u64>) -> Self {
+        Self {
+            range,
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            progress_interval: Some(Duration::from_secs(5)),
+            checkpoint: None,
+            checkpoint_interval: Duration::from_secs(30),
+            fingerprint: String::new(),
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Check the indexes in chunks of the size provided
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        assert!(chunk_size > 0, "A chunk must have at least one index");
+
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Report the progress to standard error this often
+    pub fn with_progress_interval(mut self, interval: Duration) -> Self {
+        self.progress_interval = Some(interval);
+        self
+    }
+
+    /// Do not report any progress
+    pub fn quiet(mut self) -> Self {
+        self.progress_interval = None;
+        self
+    }
+
+    /// Resume from the checkpoint file if it exists, and save the progress to it as the scan goes
+    pub fn with_checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint = Some(path.into());
+        self
+    }
+
+    /// Save the progress to the checkpoint this often, as well as when the scan is stopped
+    pub fn with_checkpoint_interval(mut self, interval: Duration) -> Self {
+        self.checkpoint_interval = interval;
+        self
+    }
+
+    /// Describe what the scan is looking for, such as the rules being checked,
+    /// so a checkpoint saved while looking for something else is not resumed
+    pub fn with_fingerprint(mut self, fingerprint: impl Into<String>) -> Self {
+        self.fingerprint = fingerprint.into();
+        self
+    }
+
+    /// Get a flag that stops the scan after its current chunks when set, just as Ctrl-C does
+    pub fn stop_handle(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+
+    /// Check if the scan has been asked to stop
+    fn is_stopped(&self) -> bool {
+        INTERRUPTED.load(Ordering::Relaxed) || self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Split the ranges of indexes into chunks
+    fn chunks(&self, ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        let chunk_size = self.chunk_size;
+
+        ranges
+            .into_iter()
+            .flat_map(|range| {
+                (range.start..range.end)
+                    .step_by(chunk_size as usize)
+                    .map(move |start| start..(start + chunk_size).min(range.end))
+            })
+            .collect()
+    }
+
+    /// Check every index left to scan, keeping the value returned for each index that is found.
+    /// The checkpoint is resumed from if it exists, and removed once the scan is complete
+    pub fn run<T, F>(&self, check: F) -> Result<ScanOutcome<T>, CheckpointError>
+    where
+        T: ScanRecord + Send,
+        F: Fn(u64) -> Option<T> + Sync,
+    {
+        install_interrupt_handler();
+
+        // A Ctrl-C that stopped an earlier scan should not stop this one
+        INTERRUPTED.store(false, Ordering::SeqCst);
+
+        let checkpoint = match &self.checkpoint {
+            Some(path) if path.exists() => {
+                Checkpoint::load(path, self.range.clone(), &self.fingerprint)?
+            }
+            _ => Checkpoint::new(self.range.clone()).with_fingerprint(self.fingerprint.clone()),
+        };
+
+        let total = self.range.end - self.range.start;
+        let resumed = checkpoint.num_completed();
+        let chunks = self.chunks(checkpoint.remaining());
+
+        let started = Instant::now();
+        let state = Mutex::new(ScanState {
+            checkpoint,
+            scanned: 0,
+            last_report: started,
+            last_save: started,
+            error: None,
+        });
+
+        // Set if the checkpoint cannot be saved, as the progress would be lost if the scan carried on
+        let failed = AtomicBool::new(false);
+
+        chunks.into_par_iter().for_each(|chunk| {
+            if self.is_stopped() || failed.load(Ordering::Relaxed) {
+                return;
+            }
+
+            let found = chunk
+                .clone()
+                .filter_map(|index| check(index).map(|value| (index, value)))
+                .collect();
+
+            let mut state = state.lock().expect("A chunk should not panic while saving");
+            state.scanned += chunk.end - chunk.start;
+            state.checkpoint.complete(chunk, found);
+
+            if let Some(interval) = self.progress_interval {
+                if state.last_report.elapsed() >= interval {
+                    eprintln!(
+                        "{}",
+                        Progress::new(total, resumed, state.scanned, started.elapsed())
+                    );
+                    state.last_report = Instant::now();
+                }
+            }
+
+            if let Some(path) = &self.checkpoint {
+                if state.last_save.elapsed() >= self.checkpoint_interval {
+                    match state.checkpoint.save(path) {
+                        Ok(()) => state.last_save = Instant::now(),
+                        Err(error) => {
+                            state.error = Some(error);
+                            failed.store(true, Ordering::Relaxed);
+                        }
+                    }
+                }
+            }
+        });
+
+        let state = state
+            .into_inner()
+            .expect("A chunk should not panic while saving");
+
+        if let Some(error) = state.error {
+            return Err(error);
+        }
+
+        let progress = Progress::new(total, resumed, state.scanned, started.elapsed());
+        if self.progress_interval.is_some() {
+            eprintln!("{}", progress);
+        }
+
+        if !state.checkpoint.is_complete() {
+            if let Some(path) = &self.checkpoint {
+                state.checkpoint.save(path)?;
+            }
+
+            return Ok(ScanOutcome::Interrupted(progress));
+        }
+
+        // A finished scan has nothing to resume
+        if let Some(path) = &self.checkpoint {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(ScanOutcome::Complete(state.checkpoint.into_found()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process, sync::atomic::Ordering};
+
+    use super::{checkpoint::CheckpointError, Scan, ScanOutcome, INTERRUPTED};
+
+    #[test]
+    fn test_resume() {
+        let path = env::temp_dir().join(format!("ritangle_scan_{}.checkpoint", process::id()));
+        let check = |index: u64| index.is_multiple_of(7).then_some(());
+
+        // A Ctrl-C from an earlier scan does not stop the next one
+        INTERRUPTED.store(true, Ordering::SeqCst);
+
+        let Ok(ScanOutcome::Complete(expected)) =
+            Scan::new(0..10_000).with_chunk_size(10).quiet().run(check)
+        else {
+            panic!("The scan should be complete");
+        };
+
+        assert_eq!(expected.len(), 1429);
+
+        // Stop part of the way through, which saves the checkpoint
+        let scan = Scan::new(0..10_000)
+            .with_chunk_size(10)
+            .quiet()
+            .with_fingerprint("every seventh")
+            .with_checkpoint(&path);
+        let stop = scan.stop_handle();
+
+        let outcome = scan.run(|index| {
+            if index == 5_000 {
+                stop.store(true, Ordering::Relaxed);
+            }
+
+            check(index)
+        });
+
+        let Ok(ScanOutcome::Interrupted(progress)) = outcome else {
+            panic!("The scan should have stopped");
+        };
+
+        assert!(progress.num_completed() < 10_000 && path.exists());
+
+        // The checkpoint cannot be used by a scan over other indexes
+        let outcome = Scan::new(0..20_000)
+            .quiet()
+            .with_checkpoint(&path)
+            .run(check);
+
+        assert!(matches!(
+            outcome,
+            Err(CheckpointError::DifferentRange { .. })
+        ));
+
+        // Or by a scan looking for something else
+        let outcome = Scan::new(0..10_000)
+            .quiet()
+            .with_fingerprint("every eleventh")
+            .with_checkpoint(&path)
+            .run(check);
+
+        assert!(matches!(
+            outcome,
+            Err(CheckpointError::DifferentFingerprint { .. })
+        ));
+
+        // Resuming finds the same values as scanning in one go
+        let outcome = Scan::new(0..10_000)
+            .with_chunk_size(10)
+            .quiet()
+            .with_fingerprint("every seventh")
+            .with_checkpoint(&path)
+            .run(check);
+
+        let Ok(ScanOutcome::Complete(found)) = outcome else {
+            panic!("The resumed scan should be complete");
+        };
+
+        assert_eq!(found, expected);
+        assert!(!path.exists());
+    }
+}
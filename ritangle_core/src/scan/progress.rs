@@ -0,0 +1,154 @@
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+/// How far through a scan is, and how quickly it is getting through the indexes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    total: u64,
+    /// The indexes scanned before this run, which were resumed from a checkpoint
+    resumed: u64,
+    /// The indexes scanned by this run
+    scanned: u64,
+    elapsed: Duration,
+}
+
+impl Progress {
+    /// Create a snapshot of a scan, where the elapsed time only covers this run
+    pub fn new(total: u64, resumed: u64, scanned: u64, elapsed: Duration) -> Self {
+        Self {
+            total,
+            resumed,
+            scanned,
+            elapsed,
+        }
+    }
+
+    /// Count how many indexes have been scanned, including those resumed from a checkpoint
+    pub fn num_completed(&self) -> u64 {
+        self.resumed + self.scanned
+    }
+
+    /// Get the number of indexes in the scan
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Get how many indexes this run has scanned each second
+    pub fn throughput(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+
+        if seconds == 0.0 {
+            return 0.0;
+        }
+
+        self.scanned as f64 / seconds
+    }
+
+    /// Estimate how long is left at the current throughput, returns None before any index is scanned
+    pub fn time_remaining(&self) -> Option<Duration> {
+        let throughput = self.throughput();
+
+        if throughput == 0.0 {
+            return None;
+        }
+
+        let remaining = self.total - self.num_completed();
+
+        Some(Duration::from_secs_f64(remaining as f64 / throughput))
+    }
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percentage = if self.total == 0 {
+            100.0
+        } else {
+            self.num_completed() as f64 / self.total as f64 * 100.0
+        };
+
+        write!(
+            f,
+            "Scanned {} of {} ({:.1}%) at {} per second",
+            self.num_completed(),
+            self.total,
+            percentage,
+            format_count(self.throughput())
+        )?;
+
+        // There is nothing to estimate once the scan is complete
+        match self.time_remaining() {
+            Some(remaining) if self.num_completed() < self.total => {
+                write!(f, ", {} left", format_duration(remaining))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Shorten a large count to millions or thousands, such as 14.8M
+fn format_count(count: f64) -> String {
+    if count >= 1e6 {
+        format!("{:.1}M", count / 1e6)
+    } else if count >= 1e3 {
+        format!("{:.1}k", count / 1e3)
+    } else {
+        format!("{:.0}", count)
+    }
+}
+
+/// Write the duration to the nearest second, such as 1h 02m 05s
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64().round() as u64;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Progress;
+
+    #[test]
+    fn test_progress() {
+        // Half of the scan was resumed, and this run has done a quarter in 10 seconds
+        let progress = Progress::new(
+            1_000_000_000,
+            500_000_000,
+            250_000_000,
+            Duration::from_secs(10),
+        );
+
+        assert_eq!(progress.throughput(), 25_000_000.0);
+        assert_eq!(progress.time_remaining(), Some(Duration::from_secs(10)));
+        assert_eq!(
+            progress.to_string(),
+            "Scanned 750000000 of 1000000000 (75.0%) at 25.0M per second, 10s left"
+        );
+
+        let progress = Progress::new(10_000_000, 0, 1_000, Duration::from_secs(1));
+
+        assert_eq!(
+            progress.to_string(),
+            "Scanned 1000 of 10000000 (0.0%) at 1.0k per second, 2h 46m 39s left"
+        );
+
+        // Nothing has been scanned yet, so there is no estimate
+        let progress = Progress::new(100, 0, 0, Duration::ZERO);
+
+        assert_eq!(progress.time_remaining(), None);
+        assert_eq!(
+            progress.to_string(),
+            "Scanned 0 of 100 (0.0%) at 0 per second"
+        );
+    }
+}
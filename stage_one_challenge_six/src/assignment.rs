@@ -0,0 +1,141 @@
+use std::fmt::{self, Display};
+
+use itertools::Itertools;
+
+use crate::triangle::{Case, KnownParts, Triangle};
+
+/// A side or an angle of a triangle, where side i is opposite angle i
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    Side(usize),
+    Angle(usize),
+}
+
+impl Part {
+    /// Every side and angle of a triangle
+    const ALL: [Part; 6] = [
+        Part::Side(0),
+        Part::Side(1),
+        Part::Side(2),
+        Part::Angle(0),
+        Part::Angle(1),
+        Part::Angle(2),
+    ];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::Side(i) => write!(f, "{}", ['a', 'b', 'c'][*i]),
+            Part::Angle(i) => write!(f, "{}", ['A', 'B', 'C'][*i]),
+        }
+    }
+}
+
+/// A way of placing the given values on the parts of a triangle, along with a triangle that has those parts
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution {
+    assignment: [(Part, f64); 3],
+    case: Case,
+    triangle: Triangle,
+}
+
+impl Solution {
+    /// Get each part along with the value placed on it, where angles are in degrees
+    pub fn assignment(&self) -> &[(Part, f64); 3] {
+        &self.assignment
+    }
+
+    /// Get the case the parts were solved as
+    pub fn case(&self) -> Case {
+        self.case
+    }
+
+    /// Get the triangle with the parts
+    pub fn triangle(&self) -> &Triangle {
+        &self.triangle
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let assignment = self
+            .assignment
+            .iter()
+            .map(|(part, value)| match part {
+                Part::Side(_) => format!("{} = {}", part, value),
+                Part::Angle(_) => format!("{} = {}°", part, value),
+            })
+            .join(", ");
+
+        write!(f, "{} {}: {}", self.case, assignment, self.triangle)
+    }
+}
+
+/// Place the three values on every combination of three parts of a triangle, treating values on angles as degrees,
+/// and solve each one. A triangle congruent to one already found is left out, however its parts are labelled
+pub fn solve_all(values: [f64; 3]) -> Vec<Solution> {
+    let mut solutions: Vec<Solution> = Vec::new();
+
+    for parts in Part::ALL.into_iter().permutations(3) {
+        let assignment = [0, 1, 2].map(|i| (parts[i], values[i]));
+
+        let known = assignment
+            .iter()
+            .fold(KnownParts::new(), |known, &(part, value)| match part {
+                Part::Side(i) => known.with_side(i, value),
+                Part::Angle(i) => known.with_angle(i, value.to_radians()),
+            });
+
+        // Three angles do not give a single triangle
+        let Some(case) = known.case() else {
+            continue;
+        };
+
+        for triangle in known.solve() {
+            if solutions
+                .iter()
+                .all(|solution| !solution.triangle.is_congruent(&triangle))
+            {
+                solutions.push(Solution {
+                    assignment,
+                    case,
+                    triangle,
+                });
+            }
+        }
+    }
+
+    solutions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solve_all;
+    use crate::triangle::Case;
+
+    #[test]
+    fn test_solve_all() {
+        let solutions = solve_all([50.0, 60.0, 70.0]);
+
+        // Both triangles from sides of 60 and 70 with 50° opposite the 60 are found
+        let ambiguous = solutions
+            .iter()
+            .filter(|solution| {
+                solution.case() == Case::SideSideAngle
+                    && solution
+                        .to_string()
+                        .starts_with("SSA A = 50°, a = 60, b = 70")
+            })
+            .count();
+
+        assert_eq!(ambiguous, 2);
+
+        // Every triangle is different
+        for (i, solution) in solutions.iter().enumerate() {
+            assert!(solutions[..i]
+                .iter()
+                .all(|other| !other.triangle().is_congruent(solution.triangle())));
+        }
+    }
+}
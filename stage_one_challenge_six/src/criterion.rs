@@ -0,0 +1,125 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::{assignment::Solution, triangle::Triangle};
+
+/// A measure of a triangle to find the best triangle by
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Criterion {
+    Perimeter,
+    Area,
+    LongestSide,
+    ShortestSide,
+}
+
+impl Criterion {
+    /// Measure the triangle
+    pub fn value(&self, triangle: &Triangle) -> f64 {
+        let sides = triangle.sides();
+
+        match self {
+            Criterion::Perimeter => triangle.perimeter(),
+            Criterion::Area => triangle.area(),
+            Criterion::LongestSide => sides.into_iter().fold(f64::NEG_INFINITY, f64::max),
+            Criterion::ShortestSide => sides.into_iter().fold(f64::INFINITY, f64::min),
+        }
+    }
+}
+
+impl Display for Criterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Criterion::Perimeter => "perimeter",
+            Criterion::Area => "area",
+            Criterion::LongestSide => "longest side",
+            Criterion::ShortestSide => "shortest side",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// The criterion asked for is not known
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownCriterion(String);
+
+impl Display for UnknownCriterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is not a criterion, use perimeter, area, longest-side or shortest-side",
+            self.0
+        )
+    }
+}
+
+impl FromStr for Criterion {
+    type Err = UnknownCriterion;
+
+    fn from_str(criterion: &str) -> Result<Self, Self::Err> {
+        match criterion {
+            "perimeter" => Ok(Criterion::Perimeter),
+            "area" => Ok(Criterion::Area),
+            "longest-side" => Ok(Criterion::LongestSide),
+            "shortest-side" => Ok(Criterion::ShortestSide),
+            _ => Err(UnknownCriterion(criterion.to_string())),
+        }
+    }
+}
+
+/// Whether the best triangle has the smallest or the largest measure
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Goal {
+    Minimise,
+    Maximise,
+}
+
+/// Order the solutions from best to worst by any measure of their triangles
+pub fn rank_by(solutions: &mut [Solution], measure: impl Fn(&Triangle) -> f64, goal: Goal) {
+    solutions.sort_by(|x, y| {
+        let order = measure(x.triangle()).total_cmp(&measure(y.triangle()));
+
+        match goal {
+            Goal::Minimise => order,
+            Goal::Maximise => order.reverse(),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rank_by, Criterion, Goal};
+    use crate::assignment::solve_all;
+
+    #[test]
+    fn test_rank_by() {
+        let mut solutions = solve_all([50.0, 60.0, 70.0]);
+
+        rank_by(
+            &mut solutions,
+            |triangle| Criterion::Perimeter.value(triangle),
+            Goal::Minimise,
+        );
+
+        // The side of 50 opposite the angle of 70° gives the smallest triangle
+        assert_eq!(
+            solutions[0].to_string(),
+            "AAS a = 50, B = 60°, A = 70°: a = 50.0000, b = 46.0802, c = 40.7604, A = 70.0000°, B = 60.0000°, C = 50.0000°"
+        );
+
+        // From two sides and an angle, the obtuse reading of side side angle is the smallest
+        let two_sides = solutions
+            .iter()
+            .find(|solution| {
+                solution
+                    .to_string()
+                    .starts_with("SSA A = 50°, a = 60, b = 70")
+            })
+            .unwrap();
+
+        assert!((Criterion::Perimeter.value(two_sides.triangle()) - 148.0).abs() < 0.1);
+        assert!("volume".parse::<Criterion>().is_err());
+    }
+}
@@ -0,0 +1,364 @@
+use std::{
+    f64::consts::PI,
+    fmt::{self, Display},
+};
+
+/// How close two lengths or angles must be to count as the same
+const TOLERANCE: f64 = 1e-9;
+
+/// Which three parts of a triangle are known, which decides how it is solved
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Case {
+    /// Three sides
+    SideSideSide,
+    /// Two sides and the angle between them
+    SideAngleSide,
+    /// Two angles and the side between them
+    AngleSideAngle,
+    /// Two angles and a side that is not between them
+    AngleAngleSide,
+    /// Two sides and an angle that is not between them, which can give two triangles
+    SideSideAngle,
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Case::SideSideSide => "SSS",
+            Case::SideAngleSide => "SAS",
+            Case::AngleSideAngle => "ASA",
+            Case::AngleAngleSide => "AAS",
+            Case::SideSideAngle => "SSA",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// The sides and angles of a triangle that are known, where side i is opposite angle i and angles are in radians
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct KnownParts {
+    sides: [Option<f64>; 3],
+    angles: [Option<f64>; 3],
+}
+
+impl KnownParts {
+    /// Create a new set of parts where nothing is known
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the length of side i
+    pub fn with_side(mut self, i: usize, length: f64) -> Self {
+        self.sides[i] = Some(length);
+        self
+    }
+
+    /// Set the size of angle i in radians
+    pub fn with_angle(mut self, i: usize, angle: f64) -> Self {
+        self.angles[i] = Some(angle);
+        self
+    }
+
+    /// Get the indexes of the parts that are known
+    fn known(parts: &[Option<f64>; 3]) -> Vec<usize> {
+        (0..3).filter(|&i| parts[i].is_some()).collect()
+    }
+
+    /// Work out which case the known parts are, returns None unless exactly three parts are known
+    /// with at least one of them a side, as three angles only fix the shape and not the size
+    pub fn case(&self) -> Option<Case> {
+        let sides = Self::known(&self.sides);
+        let angles = Self::known(&self.angles);
+
+        match (sides.as_slice(), angles.as_slice()) {
+            ([_, _, _], []) => Some(Case::SideSideSide),
+            // The angle between two sides is the one opposite the third side
+            ([i, j], [k]) if k != i && k != j => Some(Case::SideAngleSide),
+            ([_, _], [_]) => Some(Case::SideSideAngle),
+            // The side between two angles is the one opposite the third angle
+            ([i], [j, k]) if i != j && i != k => Some(Case::AngleSideAngle),
+            ([_], [_, _]) => Some(Case::AngleAngleSide),
+            _ => None,
+        }
+    }
+
+    /// Find every triangle with these parts, which is none if the parts cannot form a triangle,
+    /// and can be two for side side angle
+    pub fn solve(&self) -> Vec<Triangle> {
+        let Some(case) = self.case() else {
+            return Vec::new();
+        };
+
+        // Lengths must be positive and each angle must be less than a straight line
+        let is_valid = |part: &Option<f64>, limit: f64| part.is_none_or(|x| x > 0.0 && x < limit);
+        if !self.sides.iter().all(|side| is_valid(side, f64::INFINITY))
+            || !self.angles.iter().all(|angle| is_valid(angle, PI))
+        {
+            return Vec::new();
+        }
+
+        match case {
+            Case::SideSideSide => {
+                let [a, b, c] = self.sides.map(|side| side.unwrap());
+
+                Triangle::from_sides(a, b, c).into_iter().collect()
+            }
+            Case::SideAngleSide => {
+                let k = Self::known(&self.angles)[0];
+                let (i, j) = ((k + 1) % 3, (k + 2) % 3);
+                let (side_i, side_j) = (self.sides[i].unwrap(), self.sides[j].unwrap());
+                let angle_k = self.angles[k].unwrap();
+
+                // The law of cosines gives the side opposite the known angle
+                let mut sides = [0.0; 3];
+                sides[i] = side_i;
+                sides[j] = side_j;
+                sides[k] = (side_i.powi(2) + side_j.powi(2)
+                    - 2.0 * side_i * side_j * angle_k.cos())
+                .sqrt();
+
+                Triangle::from_sides(sides[0], sides[1], sides[2])
+                    .into_iter()
+                    .collect()
+            }
+            Case::AngleSideAngle | Case::AngleAngleSide => {
+                // The angles add up to a straight line, which gives the third angle
+                let missing = (0..3).find(|&i| self.angles[i].is_none()).unwrap();
+                let mut angles = self.angles.map(|angle| angle.unwrap_or(0.0));
+                angles[missing] = PI - angles.iter().sum::<f64>();
+
+                if angles[missing] <= TOLERANCE {
+                    return Vec::new();
+                }
+
+                let i = Self::known(&self.sides)[0];
+
+                Triangle::from_angles_and_side(angles, i, self.sides[i].unwrap())
+                    .into_iter()
+                    .collect()
+            }
+            Case::SideSideAngle => {
+                // Side i is opposite the known angle, and side j is the other known side
+                let i = Self::known(&self.angles)[0];
+                let j = (0..3).find(|&j| j != i && self.sides[j].is_some()).unwrap();
+                let k = 3 - i - j;
+
+                let (side_i, side_j) = (self.sides[i].unwrap(), self.sides[j].unwrap());
+                let angle_i = self.angles[i].unwrap();
+
+                // The law of sines gives the sine of the angle opposite side j
+                let sin_j = side_j * angle_i.sin() / side_i;
+                if sin_j > 1.0 + TOLERANCE {
+                    return Vec::new();
+                }
+
+                // The angle can be acute or obtuse, giving two triangles when both leave room for the third angle
+                let acute = sin_j.min(1.0).asin();
+                let candidates = if (1.0 - sin_j).abs() <= TOLERANCE {
+                    vec![acute]
+                } else {
+                    vec![acute, PI - acute]
+                };
+
+                candidates
+                    .into_iter()
+                    .filter(|&angle_j| PI - angle_i - angle_j > TOLERANCE)
+                    .filter_map(|angle_j| {
+                        let mut angles = [0.0; 3];
+                        angles[i] = angle_i;
+                        angles[j] = angle_j;
+                        angles[k] = PI - angle_i - angle_j;
+
+                        Triangle::from_angles_and_side(angles, i, side_i)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A solved triangle, where side i is opposite angle i and angles are in radians
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Triangle {
+    sides: [f64; 3],
+    angles: [f64; 3],
+}
+
+impl Triangle {
+    /// Create a triangle from its three sides, returns None if the longest side is too long to meet the other two
+    pub fn from_sides(a: f64, b: f64, c: f64) -> Option<Self> {
+        let sides = [a, b, c];
+        let longest = sides.iter().copied().fold(0.0, f64::max);
+
+        if sides.iter().any(|&side| side <= 0.0) || a + b + c - longest <= longest + TOLERANCE {
+            return None;
+        }
+
+        // The law of cosines gives each angle from the sides, clamped in case of rounding
+        let angles = [0, 1, 2].map(|i| {
+            let (opposite, x, y) = (sides[i], sides[(i + 1) % 3], sides[(i + 2) % 3]);
+
+            ((x.powi(2) + y.powi(2) - opposite.powi(2)) / (2.0 * x * y))
+                .clamp(-1.0, 1.0)
+                .acos()
+        });
+
+        Some(Self { sides, angles })
+    }
+
+    /// Create a triangle from all three angles and the length of side i, using the law of sines
+    fn from_angles_and_side(angles: [f64; 3], i: usize, side: f64) -> Option<Self> {
+        if angles.iter().any(|&angle| angle <= 0.0) {
+            return None;
+        }
+
+        let ratio = side / angles[i].sin();
+
+        Some(Self {
+            sides: angles.map(|angle| ratio * angle.sin()),
+            angles,
+        })
+    }
+
+    /// Get the sides a, b and c
+    pub fn sides(&self) -> [f64; 3] {
+        self.sides
+    }
+
+    /// Get the angles A, B and C in radians
+    pub fn angles(&self) -> [f64; 3] {
+        self.angles
+    }
+
+    /// Get the total length of the sides
+    pub fn perimeter(&self) -> f64 {
+        self.sides.iter().sum()
+    }
+
+    /// Get the area from two sides and the angle between them
+    pub fn area(&self) -> f64 {
+        0.5 * self.sides[0] * self.sides[1] * self.angles[2].sin()
+    }
+
+    /// Check if the other triangle is the same shape and size, no matter how its sides are labelled
+    pub fn is_congruent(&self, other: &Triangle) -> bool {
+        let sorted = |triangle: &Triangle| {
+            let mut sides = triangle.sides;
+            sides.sort_by(f64::total_cmp);
+            sides
+        };
+
+        sorted(self)
+            .iter()
+            .zip(sorted(other))
+            .all(|(x, y)| (x - y).abs() <= TOLERANCE * x.max(y))
+    }
+}
+
+impl Display for Triangle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c] = self.sides;
+        let [angle_a, angle_b, angle_c] = self.angles.map(f64::to_degrees);
+
+        write!(
+            f,
+            "a = {:.4}, b = {:.4}, c = {:.4}, A = {:.4}°, B = {:.4}°, C = {:.4}°",
+            a, b, c, angle_a, angle_b, angle_c
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Case, KnownParts, Triangle};
+
+    fn is_close(x: f64, y: f64) -> bool {
+        (x - y).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_cases() {
+        let right = Triangle::from_sides(3.0, 4.0, 5.0).unwrap();
+
+        assert!(is_close(right.angles()[2], 90f64.to_radians()));
+        assert!(is_close(right.area(), 6.0) && is_close(right.perimeter(), 12.0));
+        assert!(Triangle::from_sides(1.0, 2.0, 3.0).is_none());
+
+        // Each case gives back the 3 4 5 triangle
+        let [angle_a, angle_b, angle_c] = right.angles();
+        let cases = [
+            (
+                KnownParts::new()
+                    .with_side(0, 3.0)
+                    .with_side(1, 4.0)
+                    .with_angle(2, angle_c),
+                Case::SideAngleSide,
+            ),
+            (
+                KnownParts::new()
+                    .with_angle(0, angle_a)
+                    .with_side(2, 5.0)
+                    .with_angle(1, angle_b),
+                Case::AngleSideAngle,
+            ),
+            (
+                KnownParts::new()
+                    .with_angle(0, angle_a)
+                    .with_angle(1, angle_b)
+                    .with_side(0, 3.0),
+                Case::AngleAngleSide,
+            ),
+            (
+                KnownParts::new()
+                    .with_side(2, 5.0)
+                    .with_side(1, 4.0)
+                    .with_angle(2, angle_c),
+                Case::SideSideAngle,
+            ),
+        ];
+
+        for (parts, case) in cases {
+            assert_eq!(parts.case(), Some(case));
+
+            let triangles = parts.solve();
+
+            assert_eq!(triangles.len(), 1, "{}", case);
+            assert!(triangles[0].is_congruent(&right), "{}", triangles[0]);
+        }
+
+        // Three angles do not fix the size
+        let parts = KnownParts::new()
+            .with_angle(0, angle_a)
+            .with_angle(1, angle_b)
+            .with_angle(2, angle_c);
+
+        assert_eq!(parts.case(), None);
+        assert!(parts.solve().is_empty());
+    }
+
+    #[test]
+    fn test_side_side_angle() {
+        // A = 30°, a = 6 and b = 10 has B either acute or obtuse
+        let parts = KnownParts::new()
+            .with_side(0, 6.0)
+            .with_side(1, 10.0)
+            .with_angle(0, 30f64.to_radians());
+
+        let triangles = parts.solve();
+        let angles_b: Vec<f64> = triangles
+            .iter()
+            .map(|triangle| triangle.angles()[1].to_degrees())
+            .collect();
+
+        assert_eq!(triangles.len(), 2);
+        assert!((angles_b[0] - 56.4427).abs() < 1e-4 && (angles_b[1] - 123.5573).abs() < 1e-4);
+
+        // With a = 5 the angle B is exactly 90°, and with a = 4 side a cannot reach side c
+        assert_eq!(parts.with_side(0, 5.0).solve().len(), 1);
+        assert!(parts.with_side(0, 4.0).solve().is_empty());
+
+        // With a longer than b only the acute angle leaves room for C
+        assert_eq!(parts.with_side(0, 12.0).solve().len(), 1);
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    puzzle::Puzzle,
+    rules::variant::Variant,
+    us::{gazetteer::Gazetteer, UnitedStatesLookup},
+};
+
+/// How far in kilometres a location can be from the nearest place for it to be named after the place
+pub const MAX_PLACE_DISTANCE: f64 = 25.0;
+
+/// How the location of each variant is turned into a letter of the final code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LetterScheme {
+    /// The first letter of the nearest named place, such as N for Nashville,
+    /// where the place is within the maximum distance of the location
+    PlaceInitial,
+    /// The first letter of the state, such as T for Tennessee
+    StateInitial,
+}
+
+impl LetterScheme {
+    /// Get the letter for the location of the grid, the place initial needs the places to be provided
+    pub fn letter(&self, puzzle: &Puzzle, places: Option<&Gazetteer>) -> Option<char> {
+        let coordinate = puzzle.coordinate().ok()?;
+        let (longitude, latitude) = (coordinate.longitude(), coordinate.latitude());
+
+        let name = match self {
+            LetterScheme::PlaceInitial => {
+                let (place, distance) = places?.nearest(longitude, latitude)?;
+
+                if distance > MAX_PLACE_DISTANCE {
+                    return None;
+                }
+
+                place.name()
+            }
+            LetterScheme::StateInitial => UnitedStatesLookup::state_at(longitude, latitude)?.name(),
+        };
+
+        name.chars()
+            .next()
+            .map(|letter| letter.to_ascii_uppercase())
+    }
+}
+
+/// The letter scheme asked for is not known
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownLetterScheme(String);
+
+impl Display for UnknownLetterScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a letter scheme, use place or state", self.0)
+    }
+}
+
+impl FromStr for LetterScheme {
+    type Err = UnknownLetterScheme;
+
+    fn from_str(scheme: &str) -> Result<Self, Self::Err> {
+        match scheme {
+            "place" => Ok(LetterScheme::PlaceInitial),
+            "state" => Ok(LetterScheme::StateInitial),
+            _ => Err(UnknownLetterScheme(scheme.to_string())),
+        }
+    }
+}
+
+/// A grid for each variant along with the code made from them, if one could be made
+pub type CodeChoice = (Vec<Puzzle>, Option<FinalCode>);
+
+/// The final answer of stage one, made from the variants in order of their value of N
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalCode {
+    ordering: Vec<&'static str>,
+    code: String,
+}
+
+impl FinalCode {
+    /// Order the variants by the value of N of their grid, smallest first, and take a letter from the location of each,
+    /// where there is one grid for each variant
+    pub fn derive(
+        variants: &[Variant],
+        grids: &[Puzzle],
+        scheme: LetterScheme,
+        places: Option<&Gazetteer>,
+    ) -> Option<Self> {
+        let order = (0..variants.len()).sorted_by_key(|&index| grids[index].n_digit());
+
+        let mut ordering = Vec::with_capacity(variants.len());
+        let mut code = String::with_capacity(variants.len());
+        for index in order {
+            ordering.push(variants[index].name());
+            code.push(scheme.letter(&grids[index], places)?);
+        }
+
+        Some(Self { ordering, code })
+    }
+
+    /// Make a code for every way of choosing one grid with its value of N for each variant,
+    /// as the puzzle gives no way to pick between grids with the same value of N
+    pub fn derive_all(
+        variants: &[Variant],
+        groups: &[Vec<Puzzle>],
+        n_combo: &[usize],
+        scheme: LetterScheme,
+        places: Option<&Gazetteer>,
+    ) -> Vec<CodeChoice> {
+        groups
+            .iter()
+            .zip(n_combo)
+            .map(|(group, &n_digit)| {
+                group
+                    .iter()
+                    .filter(move |grid| grid.n_digit() == n_digit)
+                    .cloned()
+            })
+            .multi_cartesian_product()
+            .map(|grids| {
+                let code = Self::derive(variants, &grids, scheme, places);
+
+                (grids, code)
+            })
+            .collect()
+    }
+
+    /// Get the names of the variants in the order of the code
+    pub fn ordering(&self) -> &[&'static str] {
+        &self.ordering
+    }
+
+    /// Get the code, such as RTNL
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+}
+
+impl Display for FinalCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Ordering [{}]\nCode = {}",
+            self.ordering.join(", "),
+            self.code
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FinalCode, LetterScheme};
+    use crate::{puzzle::Puzzle, rules::RuleEnforcer, us::gazetteer::Gazetteer};
+
+    #[test]
+    fn test_letter() {
+        let places = Gazetteer::from_census(include_str!("us/fixtures/places.txt")).unwrap();
+
+        // 43°40'N 70°16'W, which is in Portland, Maine
+        let puzzle = Puzzle::from_digits([4, 3, 4, 6, 1, 0, 1, 0, 7]);
+
+        assert_eq!(
+            LetterScheme::PlaceInitial.letter(&puzzle, Some(&places)),
+            Some('P')
+        );
+        assert_eq!(
+            LetterScheme::StateInitial.letter(&puzzle, Some(&places)),
+            Some('M')
+        );
+
+        // The nearest place cannot be found without any places
+        assert_eq!(LetterScheme::PlaceInitial.letter(&puzzle, None), None);
+
+        // 43°40'N 71°16'W is about 80 km from Portland, so it is not named after it
+        let puzzle = Puzzle::from_digits([4, 3, 4, 6, 1, 0, 1, 1, 7]);
+
+        assert_eq!(
+            LetterScheme::PlaceInitial.letter(&puzzle, Some(&places)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_derive() {
+        // The answers of P, Q, R and S, where Q has four grids with the same value of N
+        let groups = [
+            vec![[3, 6, 1, 6, 3, 2, 4, 6, 8]],
+            vec![
+                [3, 7, 3, 6, 1, 2, 2, 6, 7],
+                [3, 7, 3, 6, 1, 2, 2, 7, 7],
+                [3, 7, 3, 6, 1, 2, 2, 8, 7],
+                [3, 7, 3, 6, 1, 2, 2, 9, 7],
+            ],
+            vec![[3, 8, 1, 5, 4, 5, 4, 5, 8]],
+            vec![[2, 7, 5, 2, 2, 7, 3, 2, 8]],
+        ]
+        .map(|group| {
+            group
+                .into_iter()
+                .map(Puzzle::from_digits)
+                .collect::<Vec<_>>()
+        });
+
+        let variants = RuleEnforcer::variants();
+
+        // There is a choice for each grid of Q, rather than one grid being picked,
+        // but only the grid at Richmond is close enough to a place to make a code
+        let codes = FinalCode::derive_all(
+            variants,
+            &groups,
+            &[3, 1, 4, 2],
+            LetterScheme::PlaceInitial,
+            Some(Gazetteer::census()),
+        );
+
+        assert_eq!(codes.len(), 4);
+
+        for ((grids, code), q_grid) in codes.iter().zip(&groups[1]) {
+            assert_eq!(grids[1].numbers(), q_grid.numbers());
+
+            if q_grid.numbers() == [3, 7, 3, 6, 1, 2, 2, 7, 7] {
+                let code = code.as_ref().unwrap();
+
+                assert_eq!(code.ordering(), ["Q", "S", "P", "R"]);
+                assert_eq!(code.code(), "RTNL");
+            } else {
+                assert_eq!(code, &None);
+            }
+        }
+
+        // No grids have these values of N
+        assert!(FinalCode::derive_all(
+            variants,
+            &groups,
+            &[1, 3, 4, 2],
+            LetterScheme::StateInitial,
+            None
+        )
+        .is_empty());
+
+        // Without the places there is no place to take a letter from
+        assert_eq!(
+            FinalCode::derive(
+                variants,
+                &groups.map(|group| group[0].clone()),
+                LetterScheme::PlaceInitial,
+                None
+            ),
+            None
+        );
+        assert!("county".parse::<LetterScheme>().is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use geo::Point;
+use geojson::{Feature, FeatureCollection, Geometry, Value};
+use ritangle_core::geography::plus_code::PlusCode;
+
+use crate::{
+    puzzle::Puzzle,
+    rules::variant::Variant,
+    us::{gazetteer::Gazetteer, UnitedStatesLookup},
+};
+
+/// Write the candidate grids of every variant as GeoJSON, so the locations can be checked in a GIS viewer
+pub struct CandidateExport<'a> {
+    features: Vec<Feature>,
+    include_states: bool,
+    states: Vec<&'static str>,
+    places: Option<&'a Gazetteer>,
+}
+
+impl<'a> CandidateExport<'a> {
+    /// Create an empty export, which also includes the polygon of each state a candidate is in if asked
+    pub fn new(include_states: bool) -> Self {
+        Self {
+            features: Vec::new(),
+            include_states,
+            states: Vec::new(),
+            places: None,
+        }
+    }
+
+    /// Also name the place nearest to each candidate
+    pub fn with_places(mut self, places: &'a Gazetteer) -> Self {
+        self.places = Some(places);
+        self
+    }
+
+    /// Add every grid of a variant as a point at the location it describes
+    pub fn add_group(&mut self, variant: &Variant, grids: &[Puzzle]) {
+        for grid in grids {
+            self.add_candidate(variant, grid);
+        }
+    }
+
+    /// Add the grid as a point at the location it describes, grids without a valid location are skipped
+    pub fn add_candidate(&mut self, variant: &Variant, grid: &Puzzle) {
+        let Ok(coordinate) = grid.coordinate() else {
+            return;
+        };
+
+        let point = Point::new(coordinate.longitude(), coordinate.latitude());
+        let mut feature = Feature::from(Geometry::new(Value::from(&point)));
+
+        feature.set_property("group", variant.name());
+        feature.set_property("n", grid.n_digit());
+        feature.set_property("numbers", grid.numbers().to_vec());
+        feature.set_property("coordinate", coordinate.to_string());
+
+        if let Ok(plus_code) = coordinate.plus_code(PlusCode::DEFAULT_LENGTH) {
+            feature.set_property("plus_code", plus_code.to_string());
+        }
+
+        if let Some((place, distance)) = self
+            .places
+            .and_then(|places| places.nearest(coordinate.longitude(), coordinate.latitude()))
+        {
+            feature.set_property("nearest_place", place.to_string());
+            feature.set_property("nearest_place_km", distance);
+        }
+
+        let state = UnitedStatesLookup::state_at(coordinate.longitude(), coordinate.latitude());
+
+        if let Some(state) = state {
+            feature.set_property("state", state.name());
+
+            // Only add the polygon of each state once, however many candidates are in it
+            if self.include_states && !self.states.contains(&state.name()) {
+                self.states.push(state.name());
+
+                let mut state_feature = Feature::from(Geometry::new(Value::from(state.geometry())));
+                state_feature.set_property("state", state.name());
+                state_feature.set_property("code", state.code());
+
+                self.features.push(state_feature);
+            }
+        }
+
+        self.features.push(feature);
+    }
+
+    /// Get the collection of every feature added
+    pub fn to_feature_collection(&self) -> FeatureCollection {
+        FeatureCollection {
+            bbox: None,
+            features: self.features.clone(),
+            foreign_members: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CandidateExport;
+    use crate::{puzzle::Puzzle, rules::RuleEnforcer};
+
+    #[test]
+    fn test_export() {
+        let variant = &RuleEnforcer::variants()[0];
+
+        // The answer to P, which is in Nashville, Tennessee
+        let grid = Puzzle::from_digits([3, 6, 1, 6, 3, 2, 4, 6, 8]);
+
+        let mut export = CandidateExport::new(true);
+        export.add_group(variant, &[grid.clone(), grid]);
+
+        let collection = export.to_feature_collection();
+
+        // The state polygon is only added once, before the first point in it
+        assert_eq!(collection.features.len(), 3);
+
+        let state = &collection.features[0];
+        assert_eq!(
+            state.property("code").and_then(|code| code.as_str()),
+            Some("TN")
+        );
+
+        let point = &collection.features[1];
+        assert_eq!(
+            point.property("group").and_then(|group| group.as_str()),
+            Some("P")
+        );
+        assert_eq!(point.property("n").and_then(|n| n.as_u64()), Some(3));
+        assert_eq!(
+            point.geometry.as_ref().map(|geometry| &geometry.value),
+            Some(&geojson::Value::Point(vec![-(86.0 + 46.0 / 60.0), 36.2]))
+        );
+    }
+}
@@ -1,116 +1,419 @@
-use std::{array, collections::HashSet};
+use std::{env, fs};
 
+use code::LetterScheme;
+use export::CandidateExport;
 use itertools::Itertools;
 use puzzle::Puzzle;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use rules::RuleEnforcer;
-use us::UnitedStatesLookup;
+use report::{OutputFormat, StageOneReport};
+use ritangle_core::{
+    geography::{coordinate::Coordinate, great_circle::KM_PER_MILE},
+    scan::{Scan, ScanOutcome},
+};
+use rules::{
+    county::CountyRestriction, diagnostics::Diagnostics, result::ValidationResult,
+    variant::Variant, RuleEnforcer,
+};
+use solver::{joint::JointSolver, Solver};
+use us::{county::CountyLookup, gazetteer::Gazetteer, UnitedStatesLookup};
 
-pub mod lookup_tables;
+pub mod code;
+pub mod export;
 pub mod puzzle;
+pub mod region;
+pub mod report;
 pub mod rules;
+pub mod solver;
 pub mod us;
 
-fn main() {
-    UnitedStatesLookup::is_within_us(0.0, 0.0);
+/// Go through every possible grid and store all the valid grids that follow the rules,
+/// saving the progress to the checkpoint if provided so an interrupted scan can be resumed.
+/// Returns None if the scan was stopped or the checkpoint could not be used
+fn brute_force(
+    checkpoint: Option<&String>,
+    border_tolerance: f64,
+) -> Option<Vec<(Puzzle, ValidationResult)>> {
+    let mut scan = Scan::new(0..Puzzle::max_permutations()).with_fingerprint(
+        RuleEnforcer::fingerprint(RuleEnforcer::variants(), border_tolerance),
+    );
+    if let Some(path) = checkpoint {
+        scan = scan.with_checkpoint(path);
+    }
+
+    // Each index is the sequence of digits for the puzzle
+    let outcome = scan.run(|index| {
+        let attempt = Puzzle::new(index);
+
+        // Apply all clues and keep the puzzle if it is valid for any kind of puzzle
+        let result = RuleEnforcer::new(&attempt)
+            .with_border_tolerance(border_tolerance)
+            .apply_all_rules();
 
-    // Go through every possible grid
-    // and store all the valid grids that follow the rules
-    let any_valid_puzzle: Vec<_> = (0..Puzzle::max_permutations())
+        result.is_any_valid().then_some(result)
+    });
+
+    match outcome {
+        Ok(ScanOutcome::Complete(found)) => Some(
+            found
+                .into_iter()
+                .map(|(index, result)| (Puzzle::new(index), result))
+                .collect(),
+        ),
+        Ok(ScanOutcome::Interrupted(progress)) => {
+            match checkpoint {
+                Some(path) => eprintln!("Stopped, run again with --checkpoint {} to resume", path),
+                None => eprintln!(
+                    "Stopped after {} grids, use --checkpoint <path> to be able to resume",
+                    progress.num_completed()
+                ),
+            }
+
+            None
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            None
+        }
+    }
+}
+
+/// Go through every possible grid and count how many grids each clue rejects
+fn diagnostics() -> Diagnostics {
+    let empty = Diagnostics::new();
+
+    (0..Puzzle::max_permutations())
         .into_par_iter()
-        // Convert the index to the sequence of digits for the puzzle
-        .map(|seed_sequence| Puzzle::new(seed_sequence))
-        // Validate the puzzle and check if follows the rules provided
-        .map(|attempt| {
-            // Create a rule enforcer
-            let mut rule_enforcer = RuleEnforcer::new(&attempt);
+        .map(Puzzle::new)
+        .fold(
+            || empty.clone(),
+            |mut diagnostics, attempt| {
+                diagnostics.record(&attempt);
+                diagnostics
+            },
+        )
+        .reduce(|| empty.clone(), Diagnostics::merge)
+}
+
+/// Group the valid grids by the variants they are valid for, then solve the variants together
+/// to find the combinations of N and the grids that follow the constraints shared between them
+fn solve_jointly(
+    any_valid_puzzle: Vec<(Puzzle, ValidationResult)>,
+    num_variants: usize,
+) -> (Vec<Vec<usize>>, Vec<Vec<Puzzle>>) {
+    // Filter out the puzzles into the kinds they are valid for
+    let mut valid_puzzle_groups: Vec<Vec<Puzzle>> =
+        (0..num_variants).map(|_| Vec::with_capacity(512)).collect();
+
+    for (puzzle, result) in any_valid_puzzle {
+        for puzzle_kind in result.valid_variants() {
+            valid_puzzle_groups[puzzle_kind].push(puzzle.clone());
+        }
+    }
 
-            // Apply all clues and see if its valid
-            let result = rule_enforcer.apply_all_rules();
+    // Choose the values of N for every kind of puzzle so the shared constraints hold
+    let joint_solutions =
+        JointSolver::new(RuleEnforcer::shared_constraints()).solve(&valid_puzzle_groups);
 
-            (attempt, result)
+    // Every grid of a kind in a joint solution has the value of N chosen for that kind
+    let valid_n_combos = joint_solutions
+        .iter()
+        .map(|solution| {
+            solution
+                .grids()
+                .iter()
+                .map(|grids| grids[0].n_digit())
+                .collect()
         })
-        // Filter out all puzzles that not valid for any rules
-        .filter(|(_, result)| result.is_any_valid())
         .collect();
 
-    // Filter out the puzzles into the kinds they are valid for
-    let mut valid_puzzle_groups: [Vec<Puzzle>; 4] = array::from_fn(|_| Vec::with_capacity(512));
+    // Keep the puzzles of each kind that are part of a joint solution
+    let n_valid_puzzle_groups = (0..num_variants)
+        .map(|group_index| {
+            joint_solutions
+                .iter()
+                .flat_map(|solution| &solution.grids()[group_index])
+                .cloned()
+                .unique_by(|puzzle| puzzle.numbers().to_vec())
+                .collect()
+        })
+        .collect();
 
-    for (puzzle, result) in any_valid_puzzle {
-        for (puzzle_kind, is_valid) in result.results().into_iter().enumerate() {
-            if !is_valid {
-                continue;
-            }
+    (valid_n_combos, n_valid_puzzle_groups)
+}
+
+/// Solve the grid once for each reading of every ambiguous clue,
+/// and report how many grids are left for its variant under each reading
+fn report_interpretations() {
+    for ambiguous_clue in RuleEnforcer::ambiguous_clues() {
+        for interpretation in ambiguous_clue.interpretations() {
+            let variants = ambiguous_clue.variants_with(interpretation);
+
+            let variant_index = variants
+                .iter()
+                .position(|variant| variant.name() == ambiguous_clue.variant())
+                .expect("The ambiguous clue should belong to a registered variant");
+
+            let num_variants = variants.len();
+            let solutions = Solver::with_variants(variants).solve();
 
-            valid_puzzle_groups[puzzle_kind].push(puzzle);
+            let (valid_n_combos, n_valid_puzzle_groups) = solve_jointly(solutions, num_variants);
+
+            println!(
+                "{} {} as {}: {} valid puzzle {} permutations, {} distinct N combinations",
+                ambiguous_clue.variant(),
+                ambiguous_clue.question(),
+                interpretation.reading(),
+                n_valid_puzzle_groups[variant_index].len(),
+                ambiguous_clue.variant(),
+                valid_n_combos.len()
+            );
         }
     }
+}
 
-    // Collect all the values of N in each puzzle kind
-    let values_of_n: [Vec<usize>; 4] = array::from_fn(|group_index| {
-        let mut unique_n_values = HashSet::new();
+/// Write the candidate grids of every variant to a GeoJSON file, with the state polygons if asked
+fn export_candidates(
+    path: &str,
+    variants: &[Variant],
+    groups: &[Vec<Puzzle>],
+    places: Option<&Gazetteer>,
+) {
+    let mut export = CandidateExport::new(env::args().any(|arg| arg == "--export-states"));
+    if let Some(places) = places {
+        export = export.with_places(places);
+    }
 
-        // Go through every valid puzzle and append the value of N to the hashset
-        for puzzle in &valid_puzzle_groups[group_index] {
-            unique_n_values.insert(puzzle.n_digit());
-        }
+    for (variant, group) in variants.iter().zip(groups) {
+        export.add_group(variant, group);
+    }
 
-        unique_n_values.into_iter().collect()
-    });
+    match fs::write(path, export.to_feature_collection().to_string()) {
+        Ok(()) => eprintln!("Exported the candidates to {}", path),
+        Err(error) => eprintln!("Could not export the candidates to {}: {}", path, error),
+    }
+}
+
+/// Rank the candidate locations of every variant against each other,
+/// for clues such as the furthest north or how far apart two locations are
+fn report_distances(variants: &[Variant], groups: &[Vec<Puzzle>]) {
+    // Name each candidate by its variant, numbering them when a variant has more than one
+    let candidates: Vec<(usize, String, Coordinate)> = variants
+        .iter()
+        .zip(groups)
+        .enumerate()
+        .flat_map(|(variant_index, (variant, group))| {
+            group.iter().enumerate().map(move |(index, puzzle)| {
+                let name = if group.len() == 1 {
+                    variant.name().to_string()
+                } else {
+                    format!("{}{}", variant.name(), index + 1)
+                };
 
-    // Filter out all invalid combinations of n orderings (all values of n must be unique)
-    let valid_n_combos: Vec<Vec<usize>> = values_of_n
-        .into_iter()
-        .multi_cartesian_product()
-        .filter(|n_combo| n_combo.iter().all_unique())
+                let coordinate = puzzle
+                    .coordinate()
+                    .expect("A valid puzzle should have a valid location");
+
+                (variant_index, name, coordinate)
+            })
+        })
         .collect();
 
-    // Filter out puzzles that do not follow the unique N rules
-    let mut n_valid_puzzle_groups: [Vec<Puzzle>; 4] =
-        array::from_fn(|i| Vec::with_capacity(valid_puzzle_groups[i].len()));
+    println!("From north to south:");
+    for (_, name, coordinate) in candidates
+        .iter()
+        .sorted_by(|(_, _, first), (_, _, second)| second.latitude().total_cmp(&first.latitude()))
+    {
+        println!("{}: {}", name, coordinate);
+    }
+
+    println!("From west to east:");
+    for (_, name, coordinate) in candidates
+        .iter()
+        .sorted_by(|(_, _, first), (_, _, second)| first.longitude().total_cmp(&second.longitude()))
+    {
+        println!("{}: {}", name, coordinate);
+    }
 
-    for (group_index, group) in valid_puzzle_groups.into_iter().enumerate() {
-        for puzzle in group {
-            if valid_n_combos
-                .iter()
-                .any(|combination| combination[group_index] == puzzle.n_digit())
-            {
-                n_valid_puzzle_groups[group_index].push(puzzle);
+    // Only compare candidates of different variants, using the ellipsoid unless Vincenty's formula fails
+    let pairs = candidates
+        .iter()
+        .tuple_combinations()
+        .filter(|((first_variant, _, _), (second_variant, _, _))| first_variant != second_variant)
+        .map(|((_, from_name, from), (_, to_name, to))| {
+            let distance = from
+                .vincenty_distance(to)
+                .unwrap_or_else(|| from.haversine_distance(to));
+
+            (from_name, from, to_name, to, distance)
+        })
+        .sorted_by(|first, second| first.4.total_cmp(&second.4));
+
+    println!("From closest to furthest apart:");
+    for (from_name, from, to_name, to, distance) in pairs {
+        println!(
+            "{} to {}: {:.1} km ({:.1} miles), Bearing: {:.1}°, Midpoint: {}",
+            from_name,
+            to_name,
+            distance,
+            distance / KM_PER_MILE,
+            from.initial_bearing(to),
+            from.midpoint(to)
+        );
+    }
+}
+
+/// Get the value after every use of the flag, such as each county in --in-county Davidson --in-county 21111
+fn flag_values(flag: &str) -> Vec<String> {
+    env::args()
+        .tuple_windows()
+        .filter(|(arg, _)| arg == flag)
+        .map(|(_, value)| value)
+        .collect()
+}
+
+fn main() {
+    // Load the state boundaries if provided, such as a more detailed Census file, before any location is checked
+    if let Some(path) = flag_values("--states").first() {
+        if let Err(error) = UnitedStatesLookup::load_states(path) {
+            eprintln!("Could not load the state boundaries: {}", error);
+            return;
+        }
+    }
+
+    UnitedStatesLookup::is_within_us(0.0, 0.0);
+
+    if env::args().any(|arg| arg == "--diagnostics") {
+        print!("{}", diagnostics());
+        return;
+    }
+
+    if env::args().any(|arg| arg == "--interpretations") {
+        report_interpretations();
+        return;
+    }
+
+    // How to write out the results
+    let output_format = match flag_values("--format").first().map(|format| format.parse()) {
+        None => OutputFormat::Text,
+        Some(Ok(format)) => format,
+        Some(Err(error)) => {
+            eprintln!("{}", error);
+            return;
+        }
+    };
+
+    // Load the named places if provided, such as a newer Census Gazetteer places file, instead of the embedded places
+    let places = match flag_values("--places").first() {
+        None => Gazetteer::census().clone(),
+        Some(path) => match Gazetteer::from_path(path) {
+            Ok(places) => places,
+            Err(error) => {
+                eprintln!("Could not load the places: {}", error);
+                return;
+            }
+        },
+    };
+
+    // The letter of each location that makes up the final code, which is the nearest place unless asked otherwise
+    let letter_scheme = match flag_values("--letters")
+        .first()
+        .map(|scheme| scheme.parse())
+    {
+        None => LetterScheme::PlaceInitial,
+        Some(Ok(scheme)) => scheme,
+        Some(Err(error)) => {
+            eprintln!("{}", error);
+            return;
+        }
+    };
+
+    // Accept locations close to the boundary so they can be checked by hand
+    let border_tolerance = match flag_values("--border-tolerance").first() {
+        None => 0.0,
+        Some(tolerance) => match tolerance.parse::<f64>() {
+            Ok(tolerance) if tolerance >= 0.0 => tolerance,
+            _ => {
+                eprintln!(
+                    "The border tolerance must be a distance in kilometres, not {}",
+                    tolerance
+                );
+                return;
             }
+        },
+    };
+
+    // Find all the valid grids, the brute force scan is kept to check the solver against
+    let checkpoint = flag_values("--checkpoint");
+    let mut any_valid_puzzle = if env::args().any(|arg| arg == "--brute-force") {
+        let Some(any_valid_puzzle) = brute_force(checkpoint.first(), border_tolerance) else {
+            return;
+        };
+
+        any_valid_puzzle
+    } else if !checkpoint.is_empty() {
+        eprintln!(
+            "Only the brute force scan can be resumed, add --brute-force to use --checkpoint"
+        );
+        return;
+    } else {
+        Solver::new()
+            .with_border_tolerance(border_tolerance)
+            .solve()
+    };
+
+    // Load the county boundaries if provided, and only keep the grids within the counties asked for
+    let counties = match flag_values("--counties")
+        .first()
+        .map(CountyLookup::from_path)
+        .transpose()
+    {
+        Ok(counties) => counties,
+        Err(error) => {
+            eprintln!("Could not load the county boundaries: {}", error);
+            return;
         }
+    };
+
+    let allowed_counties = flag_values("--in-county");
+    if !allowed_counties.is_empty() {
+        let Some(counties) = &counties else {
+            eprintln!("The county boundaries must be provided with --counties to use --in-county");
+            return;
+        };
+
+        let restriction = CountyRestriction::new(counties, &allowed_counties);
+        any_valid_puzzle.retain(|(puzzle, _)| restriction.is_satisfied(puzzle));
     }
 
-    println!("{:?}", valid_n_combos);
+    let variants = RuleEnforcer::variants();
 
-    // Output the number of valid puzzles for each kind
-    println!(
-        "{} Valid puzzle P permutations",
-        n_valid_puzzle_groups[0].len()
-    );
-    println!(
-        "{} Valid puzzle Q permutations",
-        n_valid_puzzle_groups[1].len()
-    );
-    println!(
-        "{} Valid puzzle R permutations",
-        n_valid_puzzle_groups[2].len()
-    );
-    println!(
-        "{} Valid puzzle S permutations",
-        n_valid_puzzle_groups[3].len()
+    let (valid_n_combos, n_valid_puzzle_groups) = solve_jointly(any_valid_puzzle, variants.len());
+
+    let report = StageOneReport::new(
+        variants,
+        valid_n_combos,
+        &n_valid_puzzle_groups,
+        border_tolerance,
+        counties.as_ref(),
+        Some(&places),
+        letter_scheme,
     );
 
-    for (index, group) in n_valid_puzzle_groups.iter().enumerate() {
-        for puzzle in group {
-            println!(
-                "Group: {}, N: {}, Longitude: {}, Latitude: {}, Numbers: {:?}",
-                ['P', 'Q', 'R', 'S'][index],
-                puzzle.n_digit(),
-                -puzzle.longitude(),
-                puzzle.latitude(),
-                puzzle.numbers()
-            );
-        }
+    match output_format {
+        OutputFormat::Text => print!("{}", report),
+        OutputFormat::Json => println!("{:#}", report.to_json()),
+        OutputFormat::Csv => match report.to_csv() {
+            Ok(csv) => print!("{}", csv),
+            Err(error) => eprintln!("Could not write the CSV: {}", error),
+        },
+    }
+
+    // Write the candidates out to look at on a map
+    if let Some(path) = flag_values("--export").first() {
+        export_candidates(path, variants, &n_valid_puzzle_groups, Some(&places));
+    }
+
+    if env::args().any(|arg| arg == "--distances") {
+        report_distances(variants, &n_valid_puzzle_groups);
     }
 }
@@ -0,0 +1,105 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use ritangle_core::puzzle::{
+    layout::GridLayout,
+    position::{GridPosition, IdentifierVector, PuzzleDirection, PuzzleIdentifier},
+    wall::WallDirection,
+};
+
+/// The layout of this year's grid, with 1, 3 and 5 across and 1, 2 and 4 down,
+/// where the letters A to H circle clockwise around N in the middle
+static STAGE_ONE_LAYOUT: LazyLock<StageOneLayout> = LazyLock::new(|| {
+    StageOneLayout::new(
+        GridLayout::new(
+            3,
+            3,
+            vec![
+                (PuzzleIdentifier(1), GridPosition::new(0, 0)),
+                (PuzzleIdentifier(2), GridPosition::new(1, 0)),
+                (PuzzleIdentifier(3), GridPosition::new(1, 1)),
+                (PuzzleIdentifier(4), GridPosition::new(2, 1)),
+                (PuzzleIdentifier(5), GridPosition::new(0, 2)),
+            ],
+            vec![
+                (GridPosition::new(2, 0), WallDirection::Down),
+                (GridPosition::new(0, 1), WallDirection::Right),
+                (GridPosition::new(1, 1), WallDirection::Down),
+            ],
+        ),
+        vec![
+            ('A', GridPosition::new(0, 0)),
+            ('B', GridPosition::new(1, 0)),
+            ('C', GridPosition::new(2, 0)),
+            ('D', GridPosition::new(2, 1)),
+            ('E', GridPosition::new(2, 2)),
+            ('F', GridPosition::new(1, 2)),
+            ('G', GridPosition::new(0, 2)),
+            ('H', GridPosition::new(0, 1)),
+            ('N', GridPosition::new(1, 1)),
+        ],
+    )
+});
+
+/// The shape of the stage one grid along with the cells named by letters in the clues
+pub struct StageOneLayout {
+    grid: GridLayout,
+    /// Every entry along with the indexes of its cells, in a fixed order so each entry has a slot in the grid
+    entries: Vec<(IdentifierVector, Vec<usize>)>,
+    letters: HashMap<char, usize>,
+}
+
+impl StageOneLayout {
+    /// Create a new layout from the grid and the positions of the lettered cells
+    pub fn new(grid: GridLayout, letters: Vec<(char, GridPosition)>) -> Self {
+        // Order the entries by their first cell, with across before down
+        let mut entries: Vec<(IdentifierVector, Vec<usize>)> = grid
+            .entries()
+            .map(|(vector, cells)| (vector, cells.to_vec()))
+            .collect();
+        entries
+            .sort_by_key(|(vector, cells)| (cells[0], vector.direction == PuzzleDirection::Down));
+
+        // Convert the letter positions into cell indexes
+        let letters = letters
+            .into_iter()
+            .map(|(letter, position)| (letter, grid.position_to_index(position)))
+            .collect();
+
+        Self {
+            grid,
+            entries,
+            letters,
+        }
+    }
+
+    /// Get the layout of this year's grid
+    pub fn get() -> &'static Self {
+        &STAGE_ONE_LAYOUT
+    }
+
+    /// Get the shape of the grid
+    pub fn grid(&self) -> &GridLayout {
+        &self.grid
+    }
+
+    /// Get the slot of the entry along with the indexes of its cells, returns None if there is no such entry
+    pub fn entry(&self, vector: IdentifierVector) -> Option<(usize, &[usize])> {
+        // There are only a handful of entries, so searching them is quicker than hashing
+        self.entries
+            .iter()
+            .position(|(entry, _)| *entry == vector)
+            .map(|slot| (slot, self.entries[slot].1.as_slice()))
+    }
+
+    /// Get every entry in the grid along with the indexes of its cells, in the order of their slots
+    pub fn entries(&self) -> impl Iterator<Item = (IdentifierVector, &[usize])> {
+        self.entries
+            .iter()
+            .map(|(vector, cells)| (*vector, cells.as_slice()))
+    }
+
+    /// Get the index of the cell named by the letter, returns None if no cell has that letter
+    pub fn letter_cell(&self, letter: char) -> Option<usize> {
+        self.letters.get(&letter).copied()
+    }
+}
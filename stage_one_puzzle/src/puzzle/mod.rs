@@ -1,165 +1,150 @@
-use std::{
-    array,
-    fmt::{self, Debug},
-};
+use std::fmt::{self, Debug};
 
-use position::{PuzzleDirection, PuzzlePosition};
+use layout::StageOneLayout;
+use ritangle_core::{
+    geography::coordinate::{Coordinate, CoordinateError, DegreesMinutes, Hemisphere},
+    puzzle::position::IdentifierVector,
+};
 
-pub mod position;
+pub mod layout;
 
-#[derive(Clone, Copy)]
-pub struct Puzzle([usize; Self::NUM_OF_ELEMENTS]);
+/// A completely filled stage one grid, laid out by the core `GridLayout` of its `StageOneLayout`,
+/// with a digit for every cell of the layout
+#[derive(Clone)]
+pub struct Puzzle {
+    layout: &'static StageOneLayout,
+    digits: Vec<usize>,
+    /// The digits of every entry joined together, in the order of the slots of the layout
+    joined: Vec<usize>,
+}
 
 impl Puzzle {
-    pub const NUM_OF_ELEMENTS: usize = 9;
-
-    /// Convert the index to a digit within the grid
-    fn index_to_digit(index: usize, digit_position: u8) -> usize {
-        let divisor = 10usize.pow(digit_position as u32);
-
-        (index / divisor) % 10
-    }
-
-    /// Get the max number of possible forms the grid can be expressed as
-    pub fn max_permutations() -> u32 {
-        10u32.pow(Self::NUM_OF_ELEMENTS as u32)
+    /// Get the max number of possible forms this year's grid can be expressed as
+    pub fn max_permutations() -> u64 {
+        10u64.pow(StageOneLayout::get().grid().num_cells() as u32)
     }
 
-    /// Convert a position on grid, starting at 0,0 symbolising the top left, to an array index
-    #[inline]
-    fn grid_position_to_index(column_index: usize, row_index: usize) -> usize {
-        column_index + (row_index * 3)
+    /// Get the layout of the grid
+    pub fn layout(&self) -> &'static StageOneLayout {
+        self.layout
     }
 
-    /// Convert a position on grid, starting at 0,0 symbolising the top left, to the digit stored
-    pub fn grid_position_to_digit(&self, column_index: usize, row_index: usize) -> usize {
-        self.0[Self::grid_position_to_index(column_index, row_index)]
+    /// Get the slot and the cells of the entry at the provided position
+    fn entry(&self, position: IdentifierVector) -> (usize, &'static [usize]) {
+        self.layout
+            .entry(position)
+            .expect("The position should be an entry of the grid")
     }
 
-    /// Join the list of numbers together where the first number is the most significant digit
-    /// and the last number is the least significant
-    fn join_digits(digits: &[usize]) -> usize {
-        let mut total = 0;
-        let offset = digits.len() as u32 - 1;
-
-        for (i, &num) in digits.iter().enumerate() {
-            total += 10usize.pow(offset - (i as u32)) * (num as usize);
-        }
+    /// Get the numbers along the provided position
+    pub fn numbers_at(&self, position: IdentifierVector) -> Vec<usize> {
+        let (_, cells) = self.entry(position);
 
-        return total;
+        cells.iter().map(|&i| self.digits[i]).collect()
     }
 
-    /// Get the digits in the row at the provided offsets
-    fn row(&self, index: usize, start_offset: usize, end_offset: usize) -> Vec<usize> {
-        // Create start and end indexes, including the offset
-        let start = Self::grid_position_to_index(start_offset, index);
-        let end = Self::grid_position_to_index(3 - end_offset, index);
+    /// Get the numbers joined together along the provided position
+    pub fn joined_numbers_at(&self, position: IdentifierVector) -> usize {
+        let (slot, _) = self.entry(position);
 
-        self.0[start..end].to_vec()
+        self.joined[slot]
     }
 
-    /// Get the digits as one number in the row at the provided offsets
-    fn joined_row(&self, index: usize, start_offset: usize, end_offset: usize) -> usize {
-        let numbers = self.row(index, start_offset, end_offset);
-
-        Self::join_digits(&numbers)
+    /// Get all the numbers in the grid
+    pub fn numbers(&self) -> &[usize] {
+        &self.digits
     }
 
-    /// Get the digits in the column at the provided offset
-    fn column(&self, index: usize, start_offset: usize, end_offset: usize) -> Vec<usize> {
-        let start = Self::grid_position_to_index(index, start_offset);
-        let end = Self::grid_position_to_index(index, 3 - end_offset);
-
-        return (start..end).step_by(3).map(|i| self.0[i]).collect();
+    /// Create a puzzle of this year's grid from its digits, starting at the top left and reading across each row
+    pub fn from_digits(digits: impl Into<Vec<usize>>) -> Self {
+        Self::with_layout(StageOneLayout::get(), digits.into())
     }
 
-    /// Get the digits as one number in the column at the provided offsets
-    fn joined_column(&self, index: usize, start_offset: usize, end_offset: usize) -> usize {
-        let numbers = self.column(index, start_offset, end_offset);
+    /// Create a puzzle of the layout from its digits, starting at the top left and reading across each row
+    pub fn with_layout(layout: &'static StageOneLayout, digits: Vec<usize>) -> Self {
+        assert_eq!(
+            digits.len(),
+            layout.grid().num_cells(),
+            "The puzzle must have a digit for every cell of the layout"
+        );
 
-        Self::join_digits(&numbers)
-    }
+        // Join every entry once, as the clues read the same entries many times over
+        let joined = layout
+            .entries()
+            .map(|(_, cells)| cells.iter().fold(0, |total, &i| (total * 10) + digits[i]))
+            .collect();
 
-    /// Get the numbers along the provided position
-    pub fn numbers_at(&self, position: PuzzlePosition) -> Vec<usize> {
-        return match (position.index(), position.direction()) {
-            (1, PuzzleDirection::Across) => self.row(0, 0, 0),
-            (3, PuzzleDirection::Across) => self.row(1, 1, 0),
-            (5, PuzzleDirection::Across) => self.row(2, 0, 0),
-            (1, PuzzleDirection::Down) => self.column(0, 0, 0),
-            (2, PuzzleDirection::Down) => self.column(1, 0, 1),
-            (4, PuzzleDirection::Down) => self.column(2, 1, 0),
-            (_, _) => unreachable!("Invalid position provided, which should be impossible"),
-        };
+        Self {
+            layout,
+            digits,
+            joined,
+        }
     }
 
-    /// Get the numbers joined together along the provided position
-    pub fn joined_numbers_at(&self, position: PuzzlePosition) -> usize {
-        let numbers = self.numbers_at(position);
+    /// Create a puzzle of this year's grid from an index, whose decimal digits are the digits of the grid
+    pub fn new(init_sequence: u64) -> Self {
+        let num_cells = StageOneLayout::get().grid().num_cells();
 
-        Self::join_digits(&numbers)
-    }
+        // The last cell is the last decimal digit of the index
+        let digits: Vec<usize> = (0..num_cells)
+            .rev()
+            .map(|position| ((init_sequence / 10u64.pow(position as u32)) % 10) as usize)
+            .collect();
 
-    /// Get all the numbers in the grid
-    pub fn numbers(&self) -> [usize; 9] {
-        self.0
+        Self::from_digits(digits)
     }
 
-    pub fn new(init_sequence: u32) -> Self {
-        // Convert code into a grid of numbers
-        let mut digits: [usize; 9] =
-            array::from_fn(|i| Self::index_to_digit(init_sequence.try_into().unwrap(), i as u8));
+    /// Get the digits named by the letters joined together as one number
+    pub fn joined_letters(&self, letters: &str) -> usize {
+        letters.chars().fold(0, |total, letter| {
+            let cell = self
+                .layout
+                .letter_cell(letter)
+                .expect("The letter should name a cell of the grid");
 
-        // Reverse the array to fix the ordering of the numbers
-        digits.reverse();
-
-        Self(digits)
+            (total * 10) + self.digits[cell]
+        })
     }
 
     /// Get the value of N in the puzzle
     pub fn n_digit(&self) -> usize {
-        self.0[4]
+        self.joined_letters("N")
     }
 
-    /// Get the longitude the puzzle represents
-    pub fn longitude(&self) -> f64 {
-        // EF is the degrees west and GH are the corresponding minutes
-        let mut fe = self.row(2, 1, 0);
-        let mut hg = self.column(0, 1, 0);
-
-        // Reverse the digits to get the right ordering of digits
-        fe.reverse();
-        hg.reverse();
-
-        let ef = Self::join_digits(&fe) as f64;
-        let gh = Self::join_digits(&hg) as f64;
-
-        ef + (gh / 60.0)
-    }
-
-    /// Get the latitude the puzzle represents
-    pub fn latitude(&self) -> f64 {
+    /// Get the location the puzzle represents, which fails if either number of minutes is 60 or more
+    pub fn coordinate(&self) -> Result<Coordinate, CoordinateError> {
         // AB is the degrees north and CD are the corresponding minutes
-        let ab = self.joined_row(0, 0, 1) as f64;
-        let cd = self.joined_column(2, 0, 1) as f64;
+        let latitude = DegreesMinutes::new(
+            self.joined_letters("AB") as u32,
+            self.joined_letters("CD") as u32,
+            Hemisphere::North,
+        )?;
+
+        // EF is the degrees west and GH are the corresponding minutes
+        let longitude = DegreesMinutes::new(
+            self.joined_letters("EF") as u32,
+            self.joined_letters("GH") as u32,
+            Hemisphere::West,
+        )?;
 
-        ab + (cd / 60.0)
+        Coordinate::new(latitude, longitude)
     }
 }
 
 impl Debug for Puzzle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Puzzle")
-            .field("row_one", &self.row(0, 0, 0))
-            .field("row_two", &self.row(1, 0, 0))
-            .field("row_three", &self.row(2, 0, 0))
+        f.debug_list()
+            .entries(self.digits.chunks(self.layout.grid().num_columns()))
             .finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::puzzle::position::PuzzlePosition;
+    use ritangle_core::{
+        geography::{coordinate::CoordinateError, plus_code::PlusCode},
+        puzzle::position::IdentifierVector,
+    };
 
     use super::Puzzle;
 
@@ -167,55 +152,97 @@ mod tests {
     fn test_numbers_at() {
         let puzzle = Puzzle::new(123456789);
 
-        println!("{:?}", puzzle);
+        assert_eq!(format!("{:?}", puzzle), "[[1, 2, 3], [4, 5, 6], [7, 8, 9]]");
 
         // Test 1,3,5 across
         assert_eq!(
-            puzzle.numbers_at(PuzzlePosition::new_across(1)),
-            vec![1, 2, 3]
+            puzzle.numbers_at(IdentifierVector::across(1)).as_slice(),
+            [1, 2, 3]
         );
 
-        assert_eq!(puzzle.numbers_at(PuzzlePosition::new_across(3)), vec![5, 6]);
+        assert_eq!(
+            puzzle.numbers_at(IdentifierVector::across(3)).as_slice(),
+            [5, 6]
+        );
 
         assert_eq!(
-            puzzle.numbers_at(PuzzlePosition::new_across(5)),
-            vec![7, 8, 9]
+            puzzle.numbers_at(IdentifierVector::across(5)).as_slice(),
+            [7, 8, 9]
         );
 
         // Test 1,2,4 down
         assert_eq!(
-            puzzle.numbers_at(PuzzlePosition::new_down(1)),
-            vec![1, 4, 7]
+            puzzle.numbers_at(IdentifierVector::down(1)).as_slice(),
+            [1, 4, 7]
         );
 
-        assert_eq!(puzzle.numbers_at(PuzzlePosition::new_down(2)), vec![2, 5]);
+        assert_eq!(
+            puzzle.numbers_at(IdentifierVector::down(2)).as_slice(),
+            [2, 5]
+        );
 
-        assert_eq!(puzzle.numbers_at(PuzzlePosition::new_down(4)), vec![6, 9]);
+        assert_eq!(
+            puzzle.numbers_at(IdentifierVector::down(4)).as_slice(),
+            [6, 9]
+        );
     }
 
     #[test]
     fn test_joined_numbers_at() {
         let puzzle = Puzzle::new(123456789);
 
-        println!("{:?}", puzzle);
-
         // Test 1,3,5 across
-        assert_eq!(puzzle.joined_numbers_at(PuzzlePosition::new_across(1)), 123);
+        assert_eq!(puzzle.joined_numbers_at(IdentifierVector::across(1)), 123);
 
-        assert_eq!(puzzle.joined_numbers_at(PuzzlePosition::new_across(3)), 56);
+        assert_eq!(puzzle.joined_numbers_at(IdentifierVector::across(3)), 56);
 
-        assert_eq!(puzzle.joined_numbers_at(PuzzlePosition::new_across(5)), 789);
+        assert_eq!(puzzle.joined_numbers_at(IdentifierVector::across(5)), 789);
 
         // Test 1,2,4 down
-        assert_eq!(puzzle.joined_numbers_at(PuzzlePosition::new_down(1)), 147);
+        assert_eq!(puzzle.joined_numbers_at(IdentifierVector::down(1)), 147);
+
+        assert_eq!(puzzle.joined_numbers_at(IdentifierVector::down(2)), 25);
+
+        assert_eq!(puzzle.joined_numbers_at(IdentifierVector::down(4)), 69);
+    }
+
+    #[test]
+    fn test_coordinate() {
+        // The grid for P, which is in Nashville
+        let puzzle = Puzzle::from_digits([3, 6, 1, 6, 3, 2, 4, 6, 8]);
 
-        assert_eq!(puzzle.joined_numbers_at(PuzzlePosition::new_down(2)), 25);
+        assert_eq!(
+            puzzle.coordinate().map(|coordinate| coordinate.to_string()),
+            Ok("36°12'N 86°46'W".to_string())
+        );
+
+        // CD is 65 minutes, which does not give a location
+        let puzzle = Puzzle::from_digits([3, 6, 6, 6, 3, 5, 4, 6, 8]);
 
-        assert_eq!(puzzle.joined_numbers_at(PuzzlePosition::new_down(4)), 69);
+        assert_eq!(
+            puzzle.coordinate(),
+            Err(CoordinateError::MinutesOutOfRange(65))
+        );
     }
 
     #[test]
-    fn test_joined_digits() {
-        assert_eq!(Puzzle::join_digits(&[1, 2, 3]), 123);
+    fn test_plus_code() {
+        // The answers to P, Q, R and S, where P was worked out wrongly by hand in the result notes
+        let answers = [
+            ([3, 6, 1, 6, 3, 2, 4, 6, 8], "868M662M+28"),
+            ([3, 7, 3, 6, 1, 2, 2, 7, 7], "8794GHM8+8M"),
+            ([3, 8, 1, 5, 4, 5, 4, 5, 8], "86CP7722+22"),
+            ([2, 7, 5, 2, 2, 7, 3, 2, 8], "76VVXF28+2M"),
+        ];
+
+        for (digits, code) in answers {
+            let plus_code = Puzzle::from_digits(digits)
+                .coordinate()
+                .unwrap()
+                .plus_code(PlusCode::DEFAULT_LENGTH)
+                .unwrap();
+
+            assert_eq!(plus_code.as_str(), code);
+        }
     }
 }
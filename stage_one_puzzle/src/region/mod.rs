@@ -0,0 +1,426 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::Path,
+};
+
+use geo::{
+    BoundingRect, Closest, Distance, Geometry, Haversine, HaversineClosestPoint, Point, Polygon,
+    Within,
+};
+use geojson::{Feature, FeatureCollection, GeoJson, JsonObject};
+use rstar::{
+    primitives::{GeomWithData, Rectangle},
+    RTree, AABB,
+};
+use source::{RegionError, RegionSource};
+
+pub mod source;
+
+/// A named area with a boundary, such as a US State
+#[derive(Clone, Debug)]
+pub struct Region {
+    name: String,
+    code: Option<String>,
+    properties: Vec<(String, String)>,
+    geometry: Geometry,
+}
+
+impl Region {
+    /// Create a new region with a name, an optional short code and its shape
+    pub fn new(name: String, code: Option<String>, geometry: Geometry) -> Self {
+        Self {
+            name,
+            code,
+            properties: Vec::new(),
+            geometry,
+        }
+    }
+
+    /// Get the name of the region
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the short code of the region, such as the USPS code TN
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Get a property kept from the boundary file, such as STATEFP
+    pub fn property(&self, property: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(name, _)| name == property)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get the geometry of the region
+    pub fn geometry(&self) -> &Geometry {
+        &self.geometry
+    }
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.code {
+            Some(code) => write!(f, "{} ({})", self.name, code),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// A little under the length of a degree of latitude in kilometres, so a search box is never too small
+const KM_PER_DEGREE: f64 = 111.0;
+
+/// Where a point is relative to the regions, allowing for their boundaries being simplified
+#[derive(Clone, Copy, Debug)]
+pub enum Containment<'a> {
+    /// Within the region and further than the tolerance from its boundary
+    Inside(&'a Region),
+    /// Outside every region and further than the tolerance from any boundary
+    Outside,
+    /// Within the tolerance of the boundary of a region, on either side of it, so it should be checked by hand
+    Borderline {
+        containing: Option<&'a Region>,
+        nearest: &'a Region,
+        distance: f64,
+    },
+}
+
+impl Containment<'_> {
+    /// Check if the point is inside a region or close enough to a boundary that it could be
+    pub fn is_possibly_inside(&self) -> bool {
+        !matches!(self, Containment::Outside)
+    }
+}
+
+/// The bounding box of a region along with the index of the region it belongs to
+type RegionBox = GeomWithData<Rectangle<[f64; 2]>, usize>;
+
+/// A set of regions loaded from a GeoJSON boundary file, with an R-tree over their bounding boxes
+/// so a point only needs to be checked against the few regions whose box contains it
+pub struct RegionLookup {
+    regions: Vec<Region>,
+    tree: RTree<RegionBox>,
+}
+
+impl RegionLookup {
+    /// Load the regions from a GeoJSON file
+    pub fn from_path(path: impl AsRef<Path>, source: &RegionSource) -> Result<Self, RegionError> {
+        let text = fs::read_to_string(path)?;
+
+        Self::from_geojson(&text, source)
+    }
+
+    /// Load the regions from the text of a GeoJSON feature collection
+    pub fn from_geojson(text: &str, source: &RegionSource) -> Result<Self, RegionError> {
+        let FeatureCollection { features, .. } =
+            FeatureCollection::try_from(text.parse::<GeoJson>()?)?;
+
+        // Go through each feature and keep the ones that are wanted
+        let mut regions = Vec::with_capacity(features.len());
+        for (index, feature) in features.into_iter().enumerate() {
+            if let Some(region) = Self::read_region(index, feature, source)? {
+                regions.push(region);
+            }
+        }
+
+        Ok(Self::new(regions))
+    }
+
+    /// Create a lookup over the provided regions
+    pub fn new(regions: Vec<Region>) -> Self {
+        // Find the bounding box of every region, a region without one cannot contain any point
+        let boxes = regions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, region)| {
+                let rect = region.geometry().bounding_rect()?;
+
+                Some(RegionBox::new(
+                    Rectangle::from_corners(rect.min().into(), rect.max().into()),
+                    index,
+                ))
+            })
+            .collect();
+
+        Self {
+            regions,
+            tree: RTree::bulk_load(boxes),
+        }
+    }
+
+    /// Convert a feature into a region, returns None if the feature is filtered out
+    fn read_region(
+        index: usize,
+        feature: Feature,
+        source: &RegionSource,
+    ) -> Result<Option<Region>, RegionError> {
+        let properties = feature.properties.unwrap_or_default();
+
+        let name = Self::read_property(index, &properties, source.name_property())?;
+
+        if !source.is_wanted(&name) {
+            return Ok(None);
+        }
+
+        let code = source
+            .code_property()
+            .map(|property| Self::read_property(index, &properties, property))
+            .transpose()?;
+
+        let kept_properties = source
+            .kept_properties()
+            .iter()
+            .map(|property| {
+                Self::read_property(index, &properties, property)
+                    .map(|value| (property.clone(), value))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let Some(geometry) = feature.geometry else {
+            return Err(RegionError::MissingGeometry { name });
+        };
+
+        let Ok(geometry) = Geometry::try_from(geometry) else {
+            return Err(RegionError::InvalidGeometry { name });
+        };
+
+        Ok(Some(Region {
+            name,
+            code,
+            properties: kept_properties,
+            geometry,
+        }))
+    }
+
+    /// Read a string property of a feature
+    fn read_property(
+        index: usize,
+        properties: &JsonObject,
+        property: &str,
+    ) -> Result<String, RegionError> {
+        let value = properties
+            .get(property)
+            .ok_or_else(|| RegionError::MissingProperty {
+                feature: index,
+                property: property.to_string(),
+            })?;
+
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| RegionError::PropertyNotString {
+                feature: index,
+                property: property.to_string(),
+            })
+    }
+
+    /// Get every region that was loaded, in the order of the file
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// Find the region containing the point, where longitude represents east and latitude represents north.
+    /// If regions overlap the first in the file is returned
+    pub fn region_at(&self, longitude: f64, latitude: f64) -> Option<&Region> {
+        let test_point = Point::new(longitude, latitude);
+
+        // Only check the regions whose bounding box contains the point, in the order of the file
+        self.tree
+            .locate_all_at_point(&[longitude, latitude])
+            .map(|region_box| region_box.data)
+            .filter(|&index| test_point.is_within(self.regions[index].geometry()))
+            .min()
+            .map(|index| &self.regions[index])
+    }
+
+    /// Find where the point is relative to the regions, where points within the tolerance in kilometres
+    /// of a boundary are borderline. A tolerance of 0 only gives inside or outside
+    pub fn classify(&self, longitude: f64, latitude: f64, tolerance: f64) -> Containment<'_> {
+        let containing = self.region_at(longitude, latitude);
+
+        if tolerance > 0.0 {
+            // Only regions whose bounding box is within the tolerance can have a boundary that close,
+            // a degree of longitude gets shorter away from the equator
+            let latitude_range = tolerance / KM_PER_DEGREE;
+            let longitude_range = latitude_range / latitude.to_radians().cos().max(0.01);
+
+            let envelope = AABB::from_corners(
+                [longitude - longitude_range, latitude - latitude_range],
+                [longitude + longitude_range, latitude + latitude_range],
+            );
+
+            let test_point = Point::new(longitude, latitude);
+
+            let nearest = self
+                .tree
+                .locate_in_envelope_intersecting(&envelope)
+                .map(|region_box| region_box.data)
+                .filter_map(|index| {
+                    Self::boundary_distance(self.regions[index].geometry(), test_point)
+                        .map(|distance| (index, distance))
+                })
+                // Take the first region in the file if two boundaries are the same distance away
+                .min_by(|(first_index, first), (second_index, second)| {
+                    first.total_cmp(second).then(first_index.cmp(second_index))
+                });
+
+            if let Some((index, distance)) = nearest.filter(|&(_, distance)| distance <= tolerance)
+            {
+                return Containment::Borderline {
+                    containing,
+                    nearest: &self.regions[index],
+                    distance,
+                };
+            }
+        }
+
+        match containing {
+            Some(region) => Containment::Inside(region),
+            None => Containment::Outside,
+        }
+    }
+
+    /// Find the distance in kilometres from the point to the closest boundary of the polygons of the geometry
+    fn boundary_distance(geometry: &Geometry, point: Point) -> Option<f64> {
+        let polygons: &[Polygon] = match geometry {
+            Geometry::Polygon(polygon) => std::slice::from_ref(polygon),
+            Geometry::MultiPolygon(multi_polygon) => &multi_polygon.0,
+            _ => return None,
+        };
+
+        // The boundary is made of the outside ring and the rings of every hole
+        polygons
+            .iter()
+            .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+            .filter_map(|ring| match ring.haversine_closest_point(&point) {
+                Closest::Intersection(closest) | Closest::SinglePoint(closest) => {
+                    Some(Haversine::distance(point, closest) / 1000.0)
+                }
+                Closest::Indeterminate => None,
+            })
+            .min_by(f64::total_cmp)
+    }
+
+    /// Check if the point is within any of the regions
+    pub fn contains(&self, longitude: f64, latitude: f64) -> bool {
+        self.region_at(longitude, latitude).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        source::{RegionError, RegionSource},
+        Containment, RegionLookup,
+    };
+
+    /// Two squares next to each other, named West and East
+    const SQUARES: &str = r#"{
+        "type": "FeatureCollection",
+        "features": [
+            {
+                "type": "Feature",
+                "properties": { "NAME": "West", "CODE": "W" },
+                "geometry": { "type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1], [0, 0]]] }
+            },
+            {
+                "type": "Feature",
+                "properties": { "NAME": "East", "CODE": "E" },
+                "geometry": { "type": "Polygon", "coordinates": [[[1, 0], [2, 0], [2, 1], [1, 1], [1, 0]]] }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_region_at() {
+        let source = RegionSource::new("NAME")
+            .with_code_property("CODE")
+            .keeping(&["CODE"]);
+        let lookup = RegionLookup::from_geojson(SQUARES, &source).unwrap();
+
+        assert_eq!(lookup.regions()[1].property("CODE"), Some("E"));
+        assert_eq!(lookup.regions()[1].property("NAME"), None);
+        assert_eq!(
+            lookup.region_at(0.5, 0.5).map(ToString::to_string),
+            Some("West (W)".to_string())
+        );
+        assert_eq!(
+            lookup.region_at(1.5, 0.5).map(ToString::to_string),
+            Some("East (E)".to_string())
+        );
+        assert!(lookup.region_at(2.5, 0.5).is_none());
+    }
+
+    #[test]
+    fn test_classify() {
+        let lookup = RegionLookup::from_geojson(SQUARES, &RegionSource::new("NAME")).unwrap();
+
+        assert!(matches!(
+            lookup.classify(0.5, 0.5, 5.0),
+            Containment::Inside(region) if region.name() == "West"
+        ));
+        assert!(matches!(
+            lookup.classify(3.0, 0.5, 5.0),
+            Containment::Outside
+        ));
+
+        // Just inside the shared edge, which both squares are the same distance from
+        let Containment::Borderline {
+            containing,
+            nearest,
+            distance,
+        } = lookup.classify(0.99, 0.5, 5.0)
+        else {
+            panic!("A point 1 km from the edge should be borderline");
+        };
+
+        assert_eq!(containing.map(|region| region.name()), Some("West"));
+        assert_eq!(nearest.name(), "West");
+        assert!((1.0..1.2).contains(&distance), "{} km", distance);
+
+        // Exactly on the outer edge is outside without a tolerance, but borderline with one
+        assert!(!lookup.classify(2.0, 0.5, 0.0).is_possibly_inside());
+        assert!(matches!(
+            lookup.classify(2.0, 0.5, 1.0),
+            Containment::Borderline { containing: None, nearest, .. } if nearest.name() == "East"
+        ));
+    }
+
+    #[test]
+    fn test_filters() {
+        let source = RegionSource::new("NAME").excluding(&["west"]);
+        let lookup = RegionLookup::from_geojson(SQUARES, &source).unwrap();
+
+        assert!(!lookup.contains(0.5, 0.5));
+        assert!(lookup.contains(1.5, 0.5));
+
+        let source = RegionSource::new("NAME").including(&["WEST"]);
+        let lookup = RegionLookup::from_geojson(SQUARES, &source).unwrap();
+
+        assert_eq!(lookup.regions().len(), 1);
+        assert_eq!(lookup.regions()[0].code(), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let source = RegionSource::new("STUSPS");
+
+        assert!(matches!(
+            RegionLookup::from_geojson(SQUARES, &source),
+            Err(RegionError::MissingProperty { feature: 0, .. })
+        ));
+
+        assert!(matches!(
+            RegionLookup::from_geojson("[1, 2]", &source),
+            Err(RegionError::GeoJson(_))
+        ));
+
+        assert!(matches!(
+            RegionLookup::from_path("does_not_exist.geojson", &source),
+            Err(RegionError::Io(_))
+        ));
+    }
+}
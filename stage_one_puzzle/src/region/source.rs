@@ -0,0 +1,139 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    io,
+};
+
+/// Which features of a boundary file to load, and the properties that name them
+#[derive(Clone, Debug)]
+pub struct RegionSource {
+    name_property: String,
+    code_property: Option<String>,
+    kept_properties: Vec<String>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl RegionSource {
+    /// Load every feature of the file, named by the provided property
+    pub fn new(name_property: &str) -> Self {
+        Self {
+            name_property: name_property.to_string(),
+            code_property: None,
+            kept_properties: Vec::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Also read a short code for each feature from the provided property, such as STUSPS
+    pub fn with_code_property(mut self, code_property: &str) -> Self {
+        self.code_property = Some(code_property.to_string());
+        self
+    }
+
+    /// Also keep these string properties of each feature, such as STATEFP
+    pub fn keeping(mut self, properties: &[&str]) -> Self {
+        self.kept_properties
+            .extend(properties.iter().map(|property| property.to_string()));
+        self
+    }
+
+    /// Only load the features with these names, ignoring case
+    pub fn including(mut self, names: &[&str]) -> Self {
+        self.include
+            .extend(names.iter().map(|name| name.to_lowercase()));
+        self
+    }
+
+    /// Skip the features with these names, ignoring case
+    pub fn excluding(mut self, names: &[&str]) -> Self {
+        self.exclude
+            .extend(names.iter().map(|name| name.to_lowercase()));
+        self
+    }
+
+    /// Get the property that names each feature
+    pub fn name_property(&self) -> &str {
+        &self.name_property
+    }
+
+    /// Get the property that gives the code of each feature, if there is one
+    pub fn code_property(&self) -> Option<&str> {
+        self.code_property.as_deref()
+    }
+
+    /// Get the other properties to keep from each feature
+    pub fn kept_properties(&self) -> &[String] {
+        &self.kept_properties
+    }
+
+    /// Check if the feature with this name should be loaded
+    pub fn is_wanted(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+
+        (self.include.is_empty() || self.include.contains(&name)) && !self.exclude.contains(&name)
+    }
+}
+
+/// The reasons a boundary file cannot be loaded
+#[derive(Debug)]
+pub enum RegionError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The file is not a GeoJSON feature collection, boxed as the error is large
+    GeoJson(Box<geojson::Error>),
+    /// A feature does not have the property, features are counted from 0
+    MissingProperty { feature: usize, property: String },
+    /// A feature has the property but it is not a string
+    PropertyNotString { feature: usize, property: String },
+    /// The named feature has no geometry
+    MissingGeometry { name: String },
+    /// The named feature has a geometry that cannot be used as a boundary
+    InvalidGeometry { name: String },
+}
+
+impl Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegionError::Io(error) => write!(f, "Could not read the boundary file: {}", error),
+            RegionError::GeoJson(error) => write!(f, "Invalid GeoJSON: {}", error),
+            RegionError::MissingProperty { feature, property } => {
+                write!(f, "Feature {} has no \"{}\" property", feature, property)
+            }
+            RegionError::PropertyNotString { feature, property } => {
+                write!(
+                    f,
+                    "The \"{}\" property of feature {} is not a string",
+                    property, feature
+                )
+            }
+            RegionError::MissingGeometry { name } => write!(f, "{} has no geometry", name),
+            RegionError::InvalidGeometry { name } => {
+                write!(f, "{} does not have a usable boundary", name)
+            }
+        }
+    }
+}
+
+impl Error for RegionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RegionError::Io(error) => Some(error),
+            RegionError::GeoJson(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RegionError {
+    fn from(error: io::Error) -> Self {
+        RegionError::Io(error)
+    }
+}
+
+impl From<geojson::Error> for RegionError {
+    fn from(error: geojson::Error) -> Self {
+        RegionError::GeoJson(Box::new(error))
+    }
+}
@@ -0,0 +1,516 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+use ritangle_core::geography::{coordinate::Coordinate, plus_code::PlusCode};
+use serde_json::{json, Value};
+
+use crate::{
+    code::{CodeChoice, FinalCode, LetterScheme},
+    puzzle::Puzzle,
+    region::{Containment, Region},
+    rules::variant::Variant,
+    us::{
+        county::CountyLookup,
+        gazetteer::{Gazetteer, Place},
+        UnitedStatesLookup,
+    },
+};
+
+/// How the results of stage one are written out
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Lines of text to read, as kept in the result file
+    Text,
+    /// A single JSON object
+    Json,
+    /// A CSV table with a row for each candidate
+    Csv,
+}
+
+/// The output format asked for is not known
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownFormat(String);
+
+impl Display for UnknownFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is not an output format, use text, json or csv",
+            self.0
+        )
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = UnknownFormat;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(UnknownFormat(format.to_string())),
+        }
+    }
+}
+
+/// The columns of the CSV output, one row is written for each candidate
+const CSV_HEADER: [&str; 16] = [
+    "group",
+    "n",
+    "digits",
+    "latitude",
+    "longitude",
+    "coordinate",
+    "plus_code",
+    "state",
+    "state_code",
+    "county",
+    "county_fips",
+    "nearest_place",
+    "nearest_place_km",
+    "review_boundary",
+    "review_km",
+    "n_combinations",
+];
+
+/// Everything known about the location of a grid that is part of a solution
+pub struct CandidateReport {
+    variant: usize,
+    group: &'static str,
+    puzzle: Puzzle,
+    coordinate: Coordinate,
+    plus_code: PlusCode,
+    state: Option<&'static Region>,
+    county: Option<(String, String)>,
+    nearest_place: Option<(Place, f64)>,
+    review: Option<(&'static Region, f64)>,
+}
+
+impl CandidateReport {
+    /// Look up the location of the grid of the variant, flagging it for review within the border tolerance in kilometres,
+    /// naming the county and the nearest place when the county boundaries and the places are provided
+    pub fn new(
+        variant: usize,
+        group: &'static str,
+        puzzle: Puzzle,
+        border_tolerance: f64,
+        counties: Option<&CountyLookup>,
+        places: Option<&Gazetteer>,
+    ) -> Self {
+        let coordinate = puzzle
+            .coordinate()
+            .expect("A valid puzzle should have a valid location");
+        let (longitude, latitude) = (coordinate.longitude(), coordinate.latitude());
+
+        // Flag the locations close enough to a boundary that they could be on the wrong side of it
+        let review = match UnitedStatesLookup::classify(longitude, latitude, border_tolerance) {
+            Containment::Borderline {
+                nearest, distance, ..
+            } => Some((nearest, distance)),
+            _ => None,
+        };
+
+        Self {
+            variant,
+            group,
+            puzzle,
+            coordinate,
+            plus_code: coordinate
+                .plus_code(PlusCode::DEFAULT_LENGTH)
+                .expect("The default length should be a valid plus code length"),
+            // A borderline location may be just outside every state
+            state: UnitedStatesLookup::state_at(longitude, latitude),
+            county: counties
+                .and_then(|counties| counties.county_at(longitude, latitude))
+                .map(|county| (county.name().to_string(), county.fips().to_string())),
+            nearest_place: places
+                .and_then(|places| places.nearest(longitude, latitude))
+                .map(|(place, distance)| (place.clone(), distance)),
+            review,
+        }
+    }
+
+    /// Get the N combinations this candidate is part of
+    fn n_combinations<'a>(
+        &self,
+        n_combos: &'a [Vec<usize>],
+    ) -> impl Iterator<Item = &'a Vec<usize>> {
+        let (variant, n_digit) = (self.variant, self.puzzle.n_digit());
+
+        n_combos
+            .iter()
+            .filter(move |n_combo| n_combo[variant] == n_digit)
+    }
+
+    /// Convert to a JSON object
+    fn to_json(&self, n_combos: &[Vec<usize>]) -> Value {
+        json!({
+            "group": self.group,
+            "n": self.puzzle.n_digit(),
+            "digits": self.puzzle.numbers(),
+            "latitude": self.coordinate.latitude(),
+            "longitude": self.coordinate.longitude(),
+            "coordinate": self.coordinate.to_string(),
+            "plus_code": self.plus_code.to_string(),
+            "state": self.state.map(|state| json!({
+                "name": state.name(),
+                "code": state.code(),
+            })),
+            "county": self.county.as_ref().map(|(name, fips)| json!({
+                "name": name,
+                "fips": fips,
+            })),
+            "nearest_place": self.nearest_place.as_ref().map(|(place, distance)| json!({
+                "name": place.name(),
+                "state": place.state(),
+                "km": distance,
+            })),
+            "review": self.review.map(|(boundary, distance)| json!({
+                "boundary": boundary.name(),
+                "km": distance,
+            })),
+            "n_combinations": self.n_combinations(n_combos).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Convert to a row of the CSV output, with empty fields for anything unknown
+    fn to_csv_record(&self, n_combos: &[Vec<usize>]) -> [String; CSV_HEADER.len()] {
+        let optional = |value: Option<String>| value.unwrap_or_default();
+
+        [
+            self.group.to_string(),
+            self.puzzle.n_digit().to_string(),
+            self.puzzle
+                .numbers()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            self.coordinate.latitude().to_string(),
+            self.coordinate.longitude().to_string(),
+            self.coordinate.to_string(),
+            self.plus_code.to_string(),
+            optional(self.state.map(|state| state.name().to_string())),
+            optional(
+                self.state
+                    .and_then(|state| state.code())
+                    .map(str::to_string),
+            ),
+            optional(self.county.as_ref().map(|(name, _)| name.clone())),
+            optional(self.county.as_ref().map(|(_, fips)| fips.clone())),
+            optional(
+                self.nearest_place
+                    .as_ref()
+                    .map(|(place, _)| place.to_string()),
+            ),
+            optional(
+                self.nearest_place
+                    .as_ref()
+                    .map(|(_, distance)| format!("{:.3}", distance)),
+            ),
+            optional(self.review.map(|(boundary, _)| boundary.name().to_string())),
+            optional(self.review.map(|(_, distance)| format!("{:.3}", distance))),
+            self.n_combinations(n_combos)
+                .map(|n_combo| {
+                    n_combo
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("-")
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        ]
+    }
+}
+
+impl Display for CandidateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Group: {}, N: {}, Longitude: {}, Latitude: {}, Plus Code: {}, State: ",
+            self.group,
+            self.puzzle.n_digit(),
+            self.coordinate.longitude(),
+            self.coordinate.latitude(),
+            self.plus_code
+        )?;
+
+        match self.state {
+            Some(state) => write!(f, "{}", state)?,
+            None => write!(f, "None")?,
+        }
+
+        if let Some((name, fips)) = &self.county {
+            write!(f, ", County: {} ({})", name, fips)?;
+        }
+
+        if let Some((place, distance)) = &self.nearest_place {
+            write!(f, ", Nearest Place: {} ({:.1} km)", place, distance)?;
+        }
+
+        write!(f, ", Numbers: {:?}", self.puzzle.numbers())?;
+
+        if let Some((boundary, distance)) = self.review {
+            write!(
+                f,
+                ", Review: {:.1} km from the boundary of {}",
+                distance,
+                boundary.name()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The results of stage one, from the combinations of N to the final code
+pub struct StageOneReport {
+    n_combos: Vec<Vec<usize>>,
+    counts: Vec<(&'static str, usize)>,
+    candidates: Vec<CandidateReport>,
+    /// The code of every choice of grids for each combination of N
+    codes: Vec<(Vec<usize>, Vec<CodeChoice>)>,
+}
+
+impl StageOneReport {
+    /// Gather the results from the grids of each variant that are part of a joint solution
+    pub fn new(
+        variants: &[Variant],
+        n_combos: Vec<Vec<usize>>,
+        groups: &[Vec<Puzzle>],
+        border_tolerance: f64,
+        counties: Option<&CountyLookup>,
+        places: Option<&Gazetteer>,
+        letter_scheme: LetterScheme,
+    ) -> Self {
+        let counts = variants
+            .iter()
+            .zip(groups)
+            .map(|(variant, group)| (variant.name(), group.len()))
+            .collect();
+
+        let candidates = variants
+            .iter()
+            .zip(groups)
+            .enumerate()
+            .flat_map(|(index, (variant, group))| {
+                group.iter().map(move |puzzle| {
+                    CandidateReport::new(
+                        index,
+                        variant.name(),
+                        puzzle.clone(),
+                        border_tolerance,
+                        counties,
+                        places,
+                    )
+                })
+            })
+            .collect();
+
+        // Order the variants by N and turn their locations into the final code, for every choice of grids
+        let codes = n_combos
+            .iter()
+            .map(|n_combo| {
+                (
+                    n_combo.clone(),
+                    FinalCode::derive_all(variants, groups, n_combo, letter_scheme, places),
+                )
+            })
+            .collect();
+
+        Self {
+            n_combos,
+            counts,
+            candidates,
+            codes,
+        }
+    }
+
+    /// Convert to a single JSON object
+    pub fn to_json(&self) -> Value {
+        json!({
+            "n_combinations": self.n_combos,
+            "counts": self.counts.iter().map(|(group, count)| json!({
+                "group": group,
+                "count": count,
+            })).collect::<Vec<_>>(),
+            "candidates": self.candidates
+                .iter()
+                .map(|candidate| candidate.to_json(&self.n_combos))
+                .collect::<Vec<_>>(),
+            "codes": self.codes.iter().flat_map(|(n_combo, choices)| {
+                choices.iter().map(move |(grids, final_code)| json!({
+                    "n_combination": n_combo,
+                    "digits": grids.iter().map(Puzzle::numbers).collect::<Vec<_>>(),
+                    "ordering": final_code.as_ref().map(FinalCode::ordering),
+                    "code": final_code.as_ref().map(FinalCode::code),
+                }))
+            }).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Convert to a CSV table with a row for each candidate, which lists the N combinations it is part of
+    pub fn to_csv(&self) -> Result<String, csv::Error> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+
+        writer.write_record(CSV_HEADER)?;
+        for candidate in &self.candidates {
+            writer.write_record(candidate.to_csv_record(&self.n_combos))?;
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|error| csv::Error::from(error.into_error()))?;
+
+        Ok(String::from_utf8(bytes).expect("The CSV should be written from strings"))
+    }
+}
+
+impl Display for StageOneReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?}", self.n_combos)?;
+
+        // Output the number of valid puzzles for each kind
+        for (group, count) in &self.counts {
+            writeln!(f, "{} Valid puzzle {} permutations", count, group)?;
+        }
+
+        for candidate in &self.candidates {
+            writeln!(f, "{}", candidate)?;
+        }
+
+        for (n_combo, choices) in &self.codes {
+            // Name the grids behind each code when there is more than one choice of them
+            let several = choices.len() > 1;
+            if several {
+                writeln!(f, "{} choices of grids for {:?}", choices.len(), n_combo)?;
+            }
+
+            for (grids, final_code) in choices {
+                if several {
+                    let grids = self
+                        .counts
+                        .iter()
+                        .zip(grids)
+                        .map(|((group, _), grid)| format!("{} {:?}", group, grid.numbers()))
+                        .join(", ");
+
+                    writeln!(f, "Grids {}", grids)?;
+                }
+
+                match final_code {
+                    Some(final_code) => writeln!(f, "{}", final_code)?,
+                    None if several => writeln!(f, "No code could be made from these grids")?,
+                    None => writeln!(f, "No code could be made for {:?}", n_combo)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OutputFormat, StageOneReport};
+    use crate::{
+        code::LetterScheme, puzzle::Puzzle, rules::RuleEnforcer, us::gazetteer::Gazetteer,
+    };
+
+    /// Create a report of the answers of P, Q, R and S
+    fn answer_report() -> StageOneReport {
+        let groups = [
+            [3, 6, 1, 6, 3, 2, 4, 6, 8],
+            [3, 7, 3, 6, 1, 2, 2, 7, 7],
+            [3, 8, 1, 5, 4, 5, 4, 5, 8],
+            [2, 7, 5, 2, 2, 7, 3, 2, 8],
+        ]
+        .map(|digits| vec![Puzzle::from_digits(digits)]);
+
+        StageOneReport::new(
+            RuleEnforcer::variants(),
+            vec![vec![3, 1, 4, 2]],
+            &groups,
+            0.0,
+            None,
+            Some(Gazetteer::census()),
+            LetterScheme::PlaceInitial,
+        )
+    }
+
+    #[test]
+    fn test_json() {
+        let json = answer_report().to_json();
+
+        assert_eq!(json["n_combinations"][0][1], 1);
+        assert_eq!(json["candidates"][1]["group"], "Q");
+        assert_eq!(json["candidates"][1]["state"]["code"], "VA");
+        assert_eq!(json["candidates"][1]["digits"][7], 7);
+        assert_eq!(json["candidates"][1]["county"], serde_json::Value::Null);
+        assert_eq!(
+            json["candidates"][1]["nearest_place"]["name"],
+            "Richmond city"
+        );
+        assert_eq!(json["codes"][0]["ordering"][0], "Q");
+        assert_eq!(json["codes"][0]["code"], "RTNL");
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = answer_report().to_csv().unwrap();
+        let mut lines = csv.lines();
+
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("group,n,digits,latitude,longitude,"));
+        assert_eq!(lines.count(), 4);
+        assert!(csv.contains(
+            "P,3,361632468,36.2,-86.76666666666667,36°12'N 86°46'W,868M662M+28,Tennessee,TN,,,\"Nashville-Davidson metropolitan government (balance), TN\",3.939,,,3-1-4-2"
+        ));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_code_for_every_choice() {
+        // Q has two grids with N of 1, so neither is picked over the other
+        let mut groups = [
+            [3, 6, 1, 6, 3, 2, 4, 6, 8],
+            [3, 7, 3, 6, 1, 2, 2, 7, 7],
+            [3, 8, 1, 5, 4, 5, 4, 5, 8],
+            [2, 7, 5, 2, 2, 7, 3, 2, 8],
+        ]
+        .map(|digits| vec![Puzzle::from_digits(digits)]);
+        groups[1].push(Puzzle::from_digits([3, 7, 3, 6, 1, 2, 2, 8, 7]));
+
+        let report = StageOneReport::new(
+            RuleEnforcer::variants(),
+            vec![vec![3, 1, 4, 2]],
+            &groups,
+            0.0,
+            None,
+            Some(Gazetteer::census()),
+            LetterScheme::PlaceInitial,
+        );
+
+        let text = report.to_string();
+
+        assert!(text.contains("2 choices of grids for [3, 1, 4, 2]\n"));
+        assert!(
+            text.contains("Grids P [3, 6, 1, 6, 3, 2, 4, 6, 8], Q [3, 7, 3, 6, 1, 2, 2, 8, 7], ")
+        );
+        // Only the grid of Q at Richmond is close enough to a place to make a code
+        assert_eq!(text.matches("Code = RTNL").count(), 1);
+        assert_eq!(text.matches("No code could be made").count(), 1);
+
+        let json = report.to_json();
+
+        assert_eq!(json["codes"][0]["code"], "RTNL");
+        assert_eq!(json["codes"][1]["digits"][1][7], 8);
+        assert_eq!(json["codes"][1]["code"], serde_json::Value::Null);
+    }
+}
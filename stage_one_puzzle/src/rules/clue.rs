@@ -0,0 +1,64 @@
+use ritangle_core::puzzle::position::IdentifierVector;
+
+use crate::puzzle::{layout::StageOneLayout, Puzzle};
+
+/// A single clue along with the last cell of the grid it reads,
+/// so it can be checked as soon as all of the cells it reads are known
+#[derive(Clone, Copy)]
+pub struct Clue {
+    name: &'static str,
+    last_cell: usize,
+    check: fn(&Puzzle) -> bool,
+}
+
+impl Clue {
+    /// Create a new clue that reads the provided cells of the grid
+    pub fn new(name: &'static str, cells: &[usize], check: fn(&Puzzle) -> bool) -> Self {
+        // Only the last cell is needed, as the cells are filled in order
+        let last_cell = *cells
+            .iter()
+            .max()
+            .expect("A clue must read at least one cell");
+
+        Self {
+            name,
+            last_cell,
+            check,
+        }
+    }
+
+    /// Create a new clue that reads every cell along the provided positions
+    pub fn reading(
+        name: &'static str,
+        positions: &[IdentifierVector],
+        check: fn(&Puzzle) -> bool,
+    ) -> Self {
+        let cells: Vec<usize> = positions
+            .iter()
+            .flat_map(|&position| {
+                StageOneLayout::get()
+                    .grid()
+                    .entry_cells(position)
+                    .expect("The position should be an entry of the grid")
+            })
+            .copied()
+            .collect();
+
+        Self::new(name, &cells, check)
+    }
+
+    /// Get the name of the question this clue comes from
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get the index of the last cell, reading across each row, this clue needs to be known
+    pub fn last_cell(&self) -> usize {
+        self.last_cell
+    }
+
+    /// Check if the puzzle follows this clue, only the cells read by the clue need to be filled
+    pub fn is_satisfied(&self, puzzle: &Puzzle) -> bool {
+        (self.check)(puzzle)
+    }
+}
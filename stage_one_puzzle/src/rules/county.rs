@@ -0,0 +1,49 @@
+use crate::{puzzle::Puzzle, us::county::CountyLookup};
+
+/// A location clue that only allows grids whose location is within one of a set of counties
+pub struct CountyRestriction<'a> {
+    counties: &'a CountyLookup,
+    allowed: Vec<String>,
+}
+
+impl<'a> CountyRestriction<'a> {
+    /// Only allow the counties with these names or five digit FIPS codes
+    pub fn new(counties: &'a CountyLookup, allowed: &[String]) -> Self {
+        Self {
+            counties,
+            allowed: allowed.to_vec(),
+        }
+    }
+
+    /// Check if the location of the grid is within one of the allowed counties
+    pub fn is_satisfied(&self, puzzle: &Puzzle) -> bool {
+        let Ok(coordinate) = puzzle.coordinate() else {
+            return false;
+        };
+
+        self.counties
+            .county_at(coordinate.longitude(), coordinate.latitude())
+            .is_some_and(|county| self.allowed.iter().any(|name| county.is_named(name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CountyRestriction;
+    use crate::{puzzle::Puzzle, us::county::CountyLookup};
+
+    #[test]
+    fn test_restriction() {
+        let counties =
+            CountyLookup::from_geojson(include_str!("../us/fixtures/counties.geojson")).unwrap();
+
+        // The answers to P in Nashville and R in Louisville
+        let nashville = Puzzle::from_digits([3, 6, 1, 6, 3, 2, 4, 6, 8]);
+        let louisville = Puzzle::from_digits([3, 8, 1, 5, 4, 5, 4, 5, 8]);
+
+        let restriction = CountyRestriction::new(&counties, &["Davidson".to_string()]);
+
+        assert!(restriction.is_satisfied(&nashville));
+        assert!(!restriction.is_satisfied(&louisville));
+    }
+}
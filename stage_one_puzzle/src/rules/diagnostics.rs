@@ -0,0 +1,259 @@
+use std::fmt::{self, Display};
+
+use super::{clue::Clue, RuleEnforcer};
+use crate::puzzle::{layout::StageOneLayout, Puzzle};
+
+/// Whether a single clue passed or failed on a grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClueOutcome {
+    pub name: &'static str,
+    pub passed: bool,
+}
+
+/// The outcome of every clue on a grid for each kind of puzzle, without stopping at the first failure
+#[derive(Debug)]
+pub struct Diagnosis {
+    kinds: Vec<Vec<ClueOutcome>>,
+}
+
+impl Diagnosis {
+    /// Get the outcome of every clue for the kind of puzzle, in the order the clues are registered
+    pub fn outcomes(&self, kind: usize) -> &[ClueOutcome] {
+        &self.kinds[kind]
+    }
+
+    /// Get the names of the clues that failed for the kind of puzzle
+    pub fn failed(&self, kind: usize) -> impl Iterator<Item = &'static str> + '_ {
+        self.kinds[kind]
+            .iter()
+            .filter(|outcome| !outcome.passed)
+            .map(|outcome| outcome.name)
+    }
+}
+
+/// How many grids a clue rejected over a search
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClueCount {
+    /// The number of grids the clue failed on
+    pub failed: u64,
+    /// The number of grids the clue failed on when every other clue passed,
+    /// so these grids would be valid without the clue
+    pub only_failure: u64,
+}
+
+/// Checks every clue on each grid of a search and counts how many grids each clue rejected,
+/// to find clues that may have been encoded wrongly
+#[derive(Clone)]
+pub struct Diagnostics {
+    /// The name of each registered variant of the puzzle
+    names: Vec<&'static str>,
+    /// The clues for each kind of puzzle, including the clues every kind must follow
+    clues: Vec<Vec<Clue>>,
+    /// The location is checked separately as it is slow to check
+    location: Clue,
+    /// The counts for each clue of each kind, with the location counted last
+    counts: Vec<Vec<ClueCount>>,
+    /// The number of grids recorded
+    grids: u64,
+}
+
+impl Diagnostics {
+    /// Create new diagnostics for the clues of every kind of puzzle, with nothing recorded
+    pub fn new() -> Self {
+        let base_clues = RuleEnforcer::base_clues(StageOneLayout::get());
+        let variants = RuleEnforcer::variants();
+
+        let names = variants.iter().map(|variant| variant.name()).collect();
+
+        let clues: Vec<Vec<Clue>> = variants
+            .iter()
+            .map(|variant| {
+                let mut clues = base_clues.clone();
+                clues.extend_from_slice(variant.clues());
+
+                assert!(clues.len() < u32::BITS as usize);
+
+                clues
+            })
+            .collect();
+
+        // Named apart from question twenty one of P, which is a different clue
+        let location = Clue::new(
+            "location",
+            &(0..StageOneLayout::get().grid().num_cells()).collect::<Vec<_>>(),
+            |puzzle| RuleEnforcer::new(puzzle).question_twenty_one(),
+        );
+
+        let counts = clues
+            .iter()
+            .map(|clues| vec![ClueCount::default(); clues.len() + 1])
+            .collect();
+
+        Self {
+            names,
+            clues,
+            location,
+            counts,
+            grids: 0,
+        }
+    }
+
+    /// Check every clue on the grid, including the location
+    pub fn diagnose(&self, puzzle: &Puzzle) -> Diagnosis {
+        let location = ClueOutcome {
+            name: self.location.name(),
+            passed: self.location.is_satisfied(puzzle),
+        };
+
+        Diagnosis {
+            kinds: self
+                .clues
+                .iter()
+                .map(|clues| {
+                    clues
+                        .iter()
+                        .map(|clue| ClueOutcome {
+                            name: clue.name(),
+                            passed: clue.is_satisfied(puzzle),
+                        })
+                        .chain([location])
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Count the clues that fail on the grid.
+    /// The location is only checked when it could be the only failure for a kind of puzzle,
+    /// so its failed count only covers those grids
+    pub fn record(&mut self, puzzle: &Puzzle) {
+        self.grids += 1;
+
+        // Find the failing clues for each kind as a bit set, where bit i is set if clue i failed
+        let failures: Vec<u32> = self
+            .clues
+            .iter()
+            .map(|clues| {
+                clues
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, clue)| !clue.is_satisfied(puzzle))
+                    .fold(0, |set, (i, _)| set | (1 << i))
+            })
+            .collect();
+
+        // Only check the location if a kind of puzzle has at most one other failing clue
+        let location_passed =
+            failures.iter().all(|set| set.count_ones() > 1) || self.location.is_satisfied(puzzle);
+
+        for (kind, mut set) in failures.into_iter().enumerate() {
+            let counts = &mut self.counts[kind];
+
+            if !location_passed {
+                // The location is counted last, after every other clue
+                set |= 1 << (counts.len() - 1);
+            }
+
+            let is_only_failure = set.count_ones() == 1;
+
+            // Go through every failing clue
+            while set != 0 {
+                let clue_index = set.trailing_zeros() as usize;
+                set &= set - 1;
+
+                counts[clue_index].failed += 1;
+
+                if is_only_failure {
+                    counts[clue_index].only_failure += 1;
+                }
+            }
+        }
+    }
+
+    /// Combine the counts of two diagnostics over the same clues
+    pub fn merge(mut self, other: Self) -> Self {
+        self.grids += other.grids;
+
+        for (counts, other_counts) in self.counts.iter_mut().zip(other.counts) {
+            for (count, other_count) in counts.iter_mut().zip(other_counts) {
+                count.failed += other_count.failed;
+                count.only_failure += other_count.only_failure;
+            }
+        }
+
+        self
+    }
+
+    /// Get the name and counts of every clue for the kind of puzzle, with the location last
+    pub fn counts(&self, kind: usize) -> impl Iterator<Item = (&'static str, ClueCount)> + '_ {
+        self.clues[kind]
+            .iter()
+            .chain([&self.location])
+            .map(Clue::name)
+            .zip(self.counts[kind].iter().copied())
+    }
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Clue diagnostics over {} grids", self.grids)?;
+
+        for (kind, name) in self.names.iter().enumerate() {
+            writeln!(f, "{}", name)?;
+
+            for (clue_name, count) in self.counts(kind) {
+                writeln!(
+                    f,
+                    "  {:<22} failed {:>10}  only failure {:>8}",
+                    clue_name, count.failed, count.only_failure
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Diagnostics;
+    use crate::{puzzle::Puzzle, rules::RuleEnforcer};
+
+    #[test]
+    fn test_diagnose_known_grid() {
+        let diagnostics = Diagnostics::new();
+
+        // The grid for P, which must fail at least one clue for the other kinds
+        let diagnosis = diagnostics.diagnose(&Puzzle::from_digits([3, 6, 1, 6, 3, 2, 4, 6, 8]));
+
+        assert_eq!(diagnosis.failed(0).count(), 0);
+        assert!(diagnosis.failed(1).count() > 0);
+        assert!(diagnosis.failed(3).count() > 0);
+    }
+
+    #[test]
+    fn test_record_matches_diagnosis() {
+        let mut diagnostics = Diagnostics::new();
+
+        // The grid for P passes every clue for P, so the location is always checked
+        let puzzle = Puzzle::from_digits([3, 6, 1, 6, 3, 2, 4, 6, 8]);
+        diagnostics.record(&puzzle);
+
+        let diagnosis = diagnostics.diagnose(&puzzle);
+
+        for kind in 0..RuleEnforcer::variants().len() {
+            let failed: Vec<_> = diagnosis.failed(kind).collect();
+
+            for (name, count) in diagnostics.counts(kind) {
+                assert_eq!(count.failed, failed.contains(&name) as u64);
+                assert_eq!(count.only_failure, (failed == [name]) as u64);
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::rules::{clue::Clue, variant::Variant, RuleEnforcer};
+
+/// One possible reading of an ambiguous clue
+#[derive(Clone, Copy)]
+pub struct Interpretation {
+    reading: &'static str,
+    clue: Clue,
+}
+
+impl Interpretation {
+    /// Create a new reading of an ambiguous clue, checked by the provided clue
+    pub fn new(reading: &'static str, clue: Clue) -> Self {
+        Self { reading, clue }
+    }
+
+    /// Get the name of this reading
+    pub fn reading(&self) -> &'static str {
+        self.reading
+    }
+
+    /// Get the clue this reading is checked by
+    pub fn clue(&self) -> Clue {
+        self.clue
+    }
+}
+
+/// A clue whose meaning is unclear, along with every reading of it that might have been intended.
+/// The clue is left out of its variant unless one of the readings is chosen
+pub struct AmbiguousClue {
+    variant: &'static str,
+    question: &'static str,
+    interpretations: Vec<Interpretation>,
+}
+
+impl AmbiguousClue {
+    /// Create a new ambiguous clue of the variant with the name provided
+    pub fn new(
+        variant: &'static str,
+        question: &'static str,
+        interpretations: Vec<Interpretation>,
+    ) -> Self {
+        Self {
+            variant,
+            question,
+            interpretations,
+        }
+    }
+
+    /// Get the name of the variant the clue belongs to
+    pub fn variant(&self) -> &'static str {
+        self.variant
+    }
+
+    /// Get the name of the question the clue comes from
+    pub fn question(&self) -> &'static str {
+        self.question
+    }
+
+    /// Get every possible reading of the clue
+    pub fn interpretations(&self) -> &[Interpretation] {
+        &self.interpretations
+    }
+
+    /// Get the registered variants with the reading of the clue added to its variant
+    pub fn variants_with(&self, interpretation: &Interpretation) -> Vec<Variant> {
+        let mut variants = RuleEnforcer::variants().to_vec();
+
+        variants
+            .iter_mut()
+            .find(|variant| variant.name() == self.variant)
+            .expect("The ambiguous clue should belong to a registered variant")
+            .add_reading(*interpretation);
+
+        variants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rules::RuleEnforcer;
+
+    #[test]
+    fn test_variants_with_adds_clue_to_variant() {
+        for ambiguous_clue in RuleEnforcer::ambiguous_clues() {
+            for interpretation in ambiguous_clue.interpretations() {
+                let variants = ambiguous_clue.variants_with(interpretation);
+
+                for (variant, registered) in variants.iter().zip(RuleEnforcer::variants()) {
+                    // Only the variant the clue belongs to gains the reading
+                    let extra_clues = (variant.name() == ambiguous_clue.variant()) as usize;
+
+                    assert_eq!(
+                        variant.clues().len(),
+                        registered.clues().len() + extra_clues
+                    );
+
+                    // Each reading is told apart from the others
+                    assert_eq!(
+                        variant.fingerprint().contains(interpretation.reading()),
+                        extra_clues == 1
+                    );
+                }
+            }
+        }
+    }
+}
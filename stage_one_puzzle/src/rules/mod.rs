@@ -1,43 +1,76 @@
+use std::sync::LazyLock;
+
+use clue::Clue;
+use interpretation::AmbiguousClue;
+use itertools::Itertools;
 use p::PRules;
 use q::QRules;
 use r::RRules;
 use result::ValidationResult;
 use s::SRules;
+use shared::SharedConstraint;
+use variant::Variant;
 
-use crate::us::UnitedStatesLookup;
+use crate::{puzzle::layout::StageOneLayout, us::UnitedStatesLookup};
 
 use super::Puzzle;
 
+pub mod clue;
+pub mod county;
+pub mod diagnostics;
+pub mod interpretation;
 mod p;
 mod q;
 mod r;
 pub mod result;
 mod s;
+pub mod shared;
+pub mod variant;
+
+/// Every variant of this year's grid, in the order their results are stored
+static VARIANTS: LazyLock<Vec<Variant>> = LazyLock::new(|| {
+    vec![
+        Variant::new("P", PRules::clues()),
+        Variant::new("Q", QRules::clues()),
+        Variant::new("R", RRules::clues()),
+        Variant::new("S", SRules::clues()),
+    ]
+});
 
 /// Enforces the clues as rules on the provided puzzle
 pub struct RuleEnforcer<'a> {
     puzzle: &'a Puzzle,
+    /// How close in kilometres a location can be to the boundary of the continental United States,
+    /// on either side, and still be accepted for review. At 0 only locations inside are accepted
+    border_tolerance: f64,
 }
 
 impl<'a> RuleEnforcer<'a> {
     /// Create a new rule applier with a prime cache and with the puzzle to test
     pub fn new(puzzle: &'a Puzzle) -> Self {
-        Self { puzzle }
+        Self {
+            puzzle,
+            border_tolerance: 0.0,
+        }
+    }
+
+    /// Accept locations within the distance in kilometres of the boundary of the continental United States
+    pub fn with_border_tolerance(mut self, border_tolerance: f64) -> Self {
+        self.border_tolerance = border_tolerance;
+        self
     }
 
     /// All numbers on all puzzles must not start with 0
     fn question_five(&self) -> bool {
         // For every possible position, find the first digit and make sure its not 0
-        [0, 1, 4, 5, 6]
-            .iter()
-            .all(|&i| self.puzzle.numbers()[i] != 0)
+        Self::first_cells(self.puzzle.layout()).all(|i| self.puzzle.numbers()[i] != 0)
     }
 
     /// N (the middle element) on all puzzles must be number between 1 to 4
     fn question_eighteen(&self) -> bool {
-        let middle = self.puzzle.numbers()[4];
+        let middle = self.puzzle.n_digit();
 
-        middle >= 1 && middle <= 4
+        (1..=4).contains(&middle)
     }
 
     /// The letters A to H define a location within the continental United States as follows.
@@ -47,14 +80,85 @@ impl<'a> RuleEnforcer<'a> {
     /// and GH the corresponding minutes.
     /// This function converts the values of A to H to longitude and latitude then confines them to the continental US
     fn question_twenty_one(&self) -> bool {
-        // We flip longitude to get the direction east as the puzzle represents west
-        UnitedStatesLookup::is_within_us(-self.puzzle.longitude(), self.puzzle.latitude())
+        // Minutes of 60 or more do not give a location at all
+        self.puzzle.coordinate().is_ok_and(|coordinate| {
+            UnitedStatesLookup::classify(
+                coordinate.longitude(),
+                coordinate.latitude(),
+                self.border_tolerance,
+            )
+            .is_possibly_inside()
+        })
+    }
+
+    /// Get the indexes of the first cell of every entry in the grid
+    fn first_cells(layout: &StageOneLayout) -> impl Iterator<Item = usize> + '_ {
+        layout.entries().map(|(_, cells)| cells[0])
+    }
+
+    /// Get the clues every kind of puzzle with the layout must follow, apart from the location which is checked last
+    pub fn base_clues(layout: &StageOneLayout) -> Vec<Clue> {
+        vec![
+            Clue::new(
+                "question_five",
+                &Self::first_cells(layout).collect::<Vec<_>>(),
+                |puzzle| RuleEnforcer::new(puzzle).question_five(),
+            ),
+            Clue::new(
+                "question_eighteen",
+                &[layout
+                    .letter_cell('N')
+                    .expect("N should name a cell of the grid")],
+                |puzzle| RuleEnforcer::new(puzzle).question_eighteen(),
+            ),
+        ]
+    }
+
+    /// Get every registered variant of the grid, in the order their results are stored
+    pub fn variants() -> &'static [Variant] {
+        &VARIANTS
+    }
+
+    /// Describe every rule applied to the variants on one line, including the border tolerance,
+    /// so a scan can tell if its checkpoint was saved under other rules
+    pub fn fingerprint(variants: &[Variant], border_tolerance: f64) -> String {
+        let base_clues = Self::base_clues(StageOneLayout::get())
+            .iter()
+            .map(Clue::name)
+            .join(",");
+        let variants = variants.iter().map(Variant::fingerprint).join(" ");
+
+        format!(
+            "base({}) {} border({} km)",
+            base_clues, variants, border_tolerance
+        )
+    }
+
+    /// Get the constraints between the grids of every variant
+    pub fn shared_constraints() -> Vec<SharedConstraint> {
+        vec![
+            // All values of N are distinct
+            SharedConstraint::new("question_eighteen", Puzzle::n_digit, |values| {
+                values.iter().all_unique()
+            }),
+        ]
+    }
+
+    /// Get every clue that is unclear, along with its possible readings.
+    /// These clues are left out of their variants unless a reading is chosen
+    pub fn ambiguous_clues() -> Vec<AmbiguousClue> {
+        QRules::ambiguous_clues()
     }
 
     /// Apply all the rules for all kinds of the puzzle
     pub fn apply_all_rules(&mut self) -> ValidationResult {
+        self.apply_rules(Self::variants())
+    }
+
+    /// Apply all the rules for the provided variants of the puzzle
+    pub fn apply_rules(&mut self, variants: &[Variant]) -> ValidationResult {
         // Create a invalid result
-        let mut result = ValidationResult::new_invalid();
+        let mut result = ValidationResult::new_invalid(variants.len());
 
         // All puzzles must follow these rules
         let follows_base_rules = self.question_five() && self.question_eighteen();
@@ -66,24 +170,16 @@ impl<'a> RuleEnforcer<'a> {
         }
 
         // Apply rules for all kinds of puzzle and store them in our result
-        PRules::new(&self.puzzle).apply(result.p_mut());
-        QRules::new(&self.puzzle).apply(result.q_mut());
-        RRules::new(&self.puzzle).apply(result.r_mut());
-        SRules::new(&self.puzzle).apply(result.s_mut());
+        for (index, variant) in variants.iter().enumerate() {
+            result.set_valid(index, variant.is_valid(self.puzzle));
+        }
 
         // Check the GPS position last because it takes an ungodly amount of time
         if !result.is_any_valid() || !self.question_twenty_one() {
-            return ValidationResult::new_invalid();
-        }
-
-        if self.puzzle.grid_position_to_digit(1, 1) == 2 {
-            // The only valid puzzle of S has N of 2, so we can limit the set further for the others
-            let mut only_s = ValidationResult::new_invalid();
-            *only_s.s_mut() = true;
-            return only_s;
+            return ValidationResult::new_invalid(variants.len());
         }
 
         // && self.question_four()
-        return result;
+        result
     }
 }
@@ -1,8 +1,10 @@
-use crate::{
-    lookup_tables::LookupTables,
-    puzzle::{position::PuzzlePosition, Puzzle},
+use ritangle_core::{
+    lookup_tables::{primes::PrimeTable, square::SquareTable, triangular::TriangularTable},
+    puzzle::position::IdentifierVector,
 };
 
+use crate::{puzzle::Puzzle, rules::clue::Clue};
+
 /// The rules that a puzzle must follow to be valid as P
 pub struct PRules<'a> {
     puzzle: &'a Puzzle,
@@ -10,18 +12,18 @@ pub struct PRules<'a> {
 
 impl<'a> PRules<'a> {
     /// Create a new rule set to be applied on a puzzle for P
-    pub fn new(puzzle: &'a Puzzle) -> PRules {
+    pub fn new(puzzle: &'a Puzzle) -> PRules<'a> {
         PRules { puzzle }
     }
 
     /// 1 Down must be a prime multiple of 4 down
     fn question_four(&self) -> bool {
         // Get the two columns
-        let one_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(1));
-        let four_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(4));
+        let one_down = self.puzzle.joined_numbers_at(IdentifierVector::down(1));
+        let four_down = self.puzzle.joined_numbers_at(IdentifierVector::down(4));
 
         // Check if 4 down is divisible by 1 down
-        if one_down % four_down != 0 {
+        if four_down == 0 || !one_down.is_multiple_of(four_down) {
             // This is clearly not a prime multiple
             return false;
         }
@@ -30,49 +32,49 @@ impl<'a> PRules<'a> {
         let potential_prime = one_down / four_down;
 
         // Check if its a prime
-        LookupTables::is_prime(potential_prime)
+        PrimeTable::is_prime(potential_prime)
     }
 
     /// 2 Down has a square digit sum
     fn question_eight(&self) -> bool {
         // Get 2 Down as digits
-        let two_down = self.puzzle.numbers_at(PuzzlePosition::new_down(2));
+        let two_down = self.puzzle.numbers_at(IdentifierVector::down(2));
 
         // Sum two down
         let sum = two_down.iter().sum();
 
         // Check if the sum is a square number
-        LookupTables::is_square(sum)
+        SquareTable::is_square(sum)
     }
 
     /// 1 Across is a square
     fn question_ten(&self) -> bool {
         // Get 1 Across
-        let one_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(1));
+        let one_across = self.puzzle.joined_numbers_at(IdentifierVector::across(1));
 
         // Check if 1 Across is a square
-        LookupTables::is_square(one_across)
+        SquareTable::is_square(one_across)
     }
 
     /// 3 Across is the reverse of a prime
     fn question_twelve(&self) -> bool {
         // Get 3 Across as digits
-        let three_across = self.puzzle.numbers_at(PuzzlePosition::new_across(3));
+        let three_across = self.puzzle.numbers_at(IdentifierVector::across(3));
 
         // Join them back up together but reversed (its reverse because i starts at 0 but we start with the most significant digit, therefore it becomes the first digit)
         let mut reversed_three_ac = 0;
         for (i, &num) in three_across.iter().enumerate() {
-            reversed_three_ac += 10usize.pow(i as u32) * (num as usize);
+            reversed_three_ac += 10usize.pow(i as u32) * num;
         }
 
         // Check if reversed_three_ac a prime
-        LookupTables::is_prime(reversed_three_ac)
+        PrimeTable::is_prime(reversed_three_ac)
     }
 
     /// 5 Across is divisible by the sum of 9 puzzle digits
     fn question_twenty_one(&self) -> bool {
         // Get 5 Across
-        let five_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(5));
+        let five_across = self.puzzle.joined_numbers_at(IdentifierVector::across(5));
 
         // Get the sum of every digit in the puzzle
         let all_digits_sum = self.puzzle.numbers().iter().sum::<usize>();
@@ -87,24 +89,47 @@ impl<'a> PRules<'a> {
         }
 
         // Now if 5 Across is divisible by the sum then this is valid
-        five_across % all_digits_sum == 0
+        five_across.is_multiple_of(all_digits_sum)
     }
 
     /// 4 Down should be triangular
     fn question_twenty_five(&self) -> bool {
         // Get 4 Down
-        let four_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(4));
+        let four_down = self.puzzle.joined_numbers_at(IdentifierVector::down(4));
 
-        LookupTables::is_triangular(four_down)
+        TriangularTable::is_triangular(four_down)
     }
 
-    /// Apply all the rules for Q and store whether it was successful
-    pub fn apply(&self, is_valid: &mut bool) {
-        *is_valid = self.question_four()
-            && self.question_eight()
-            && self.question_ten()
-            && self.question_twelve()
-            && self.question_twenty_one()
-            && self.question_twenty_five();
+    /// Get every clue for P along with the positions each clue reads
+    pub fn clues() -> Vec<Clue> {
+        vec![
+            Clue::reading(
+                "question_four",
+                &[IdentifierVector::down(1), IdentifierVector::down(4)],
+                |puzzle| PRules::new(puzzle).question_four(),
+            ),
+            Clue::reading("question_eight", &[IdentifierVector::down(2)], |puzzle| {
+                PRules::new(puzzle).question_eight()
+            }),
+            Clue::reading("question_ten", &[IdentifierVector::across(1)], |puzzle| {
+                PRules::new(puzzle).question_ten()
+            }),
+            Clue::reading(
+                "question_twelve",
+                &[IdentifierVector::across(3)],
+                |puzzle| PRules::new(puzzle).question_twelve(),
+            ),
+            // The sum of every digit in the grid is needed
+            Clue::new(
+                "question_twenty_one",
+                &[0, 1, 2, 3, 4, 5, 6, 7, 8],
+                |puzzle| PRules::new(puzzle).question_twenty_one(),
+            ),
+            Clue::reading(
+                "question_twenty_five",
+                &[IdentifierVector::down(4)],
+                |puzzle| PRules::new(puzzle).question_twenty_five(),
+            ),
+        ]
     }
 }
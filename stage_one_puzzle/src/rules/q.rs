@@ -1,6 +1,11 @@
+use ritangle_core::{lookup_tables::primes::PrimeTable, puzzle::position::IdentifierVector};
+
 use crate::{
-    lookup_tables::LookupTables,
-    puzzle::{position::PuzzlePosition, Puzzle},
+    puzzle::Puzzle,
+    rules::{
+        clue::Clue,
+        interpretation::{AmbiguousClue, Interpretation},
+    },
 };
 
 /// The rules that a puzzle must follow to be valid as Q
@@ -10,14 +15,14 @@ pub struct QRules<'a> {
 
 impl<'a> QRules<'a> {
     /// Create a new rule set to be applied on a puzzle for Q
-    pub fn new(puzzle: &'a Puzzle) -> QRules {
+    pub fn new(puzzle: &'a Puzzle) -> QRules<'a> {
         QRules { puzzle }
     }
 
     /// 1 Across must be palindromic prime
     fn question_seven(&self) -> bool {
         // Get 1ac digits
-        let one_across_position = PuzzlePosition::new_across(1);
+        let one_across_position = IdentifierVector::across(1);
         let one_across_digits = self.puzzle.numbers_at(one_across_position);
 
         // Check if the first digit is equal to the last
@@ -27,13 +32,13 @@ impl<'a> QRules<'a> {
 
         let potential_prime = self.puzzle.joined_numbers_at(one_across_position);
 
-        LookupTables::is_prime(potential_prime)
+        PrimeTable::is_prime(potential_prime)
     }
 
     /// 4 Down must be a cube
     fn question_nine(&self) -> bool {
         // Get 4dn
-        let four_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(4));
+        let four_down = self.puzzle.joined_numbers_at(IdentifierVector::down(4));
 
         // 27 and 64 are the only 2 digit cubes
         [27, 64].contains(&four_down)
@@ -41,12 +46,15 @@ impl<'a> QRules<'a> {
 
     /// 1 Down must be 1 Across - the digit of sum of 1 Down
     fn question_fourteen(&self) -> bool {
-        let one_down_position = PuzzlePosition::new_down(1);
+        let one_down_position = IdentifierVector::down(1);
 
         let one_down_digits = self.puzzle.numbers_at(one_down_position);
-        let one_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(1));
+        let one_across = self.puzzle.joined_numbers_at(IdentifierVector::across(1));
 
-        let total = one_across - one_down_digits.iter().sum::<usize>();
+        // 1 Across cannot be smaller than the digit sum when it has no leading zero
+        let Some(total) = one_across.checked_sub(one_down_digits.iter().sum::<usize>()) else {
+            return false;
+        };
 
         let one_down = self.puzzle.joined_numbers_at(one_down_position);
 
@@ -56,27 +64,94 @@ impl<'a> QRules<'a> {
     /// 2 Down is a prime
     fn question_seventeen(&self) -> bool {
         // Get 2 down
-        let two_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(2));
+        let two_down = self.puzzle.joined_numbers_at(IdentifierVector::down(2));
+
+        PrimeTable::is_prime(two_down)
+    }
+
+    /// 3 Across "sees" 5 Across, read as the digits of 3 Across appearing in order within 5 Across
+    fn question_twenty_two_appears_in(&self) -> bool {
+        // Get the digits of 3 Across and 5 Across
+        let three_across_digits = self.puzzle.numbers_at(IdentifierVector::across(3));
+        let five_across_digits = self.puzzle.numbers_at(IdentifierVector::across(5));
+
+        five_across_digits
+            .windows(three_across_digits.len())
+            .any(|window| window == three_across_digits.as_slice())
+    }
+
+    /// 3 Across "sees" 5 Across, read as 3 Across seeing itself reflected within 5 Across
+    fn question_twenty_two_reversed_in(&self) -> bool {
+        // Get the digits of 3 Across reversed and 5 Across
+        let mut three_across_digits = self.puzzle.numbers_at(IdentifierVector::across(3));
+        three_across_digits.reverse();
+
+        let five_across_digits = self.puzzle.numbers_at(IdentifierVector::across(5));
+
+        five_across_digits
+            .windows(three_across_digits.len())
+            .any(|window| window == three_across_digits.as_slice())
+    }
+
+    /// 3 Across "sees" 5 Across, read as 3 Across and 5 Across sharing a digit
+    fn question_twenty_two_shares_digit(&self) -> bool {
+        let three_across_digits = self.puzzle.numbers_at(IdentifierVector::across(3));
+        let five_across_digits = self.puzzle.numbers_at(IdentifierVector::across(5));
+
+        three_across_digits
+            .iter()
+            .any(|digit| five_across_digits.contains(digit))
+    }
 
-        LookupTables::is_prime(two_down)
+    /// Get every clue for Q along with the positions each clue reads
+    pub fn clues() -> Vec<Clue> {
+        vec![
+            Clue::reading("question_seven", &[IdentifierVector::across(1)], |puzzle| {
+                QRules::new(puzzle).question_seven()
+            }),
+            Clue::reading("question_nine", &[IdentifierVector::down(4)], |puzzle| {
+                QRules::new(puzzle).question_nine()
+            }),
+            Clue::reading(
+                "question_fourteen",
+                &[IdentifierVector::down(1), IdentifierVector::across(1)],
+                |puzzle| QRules::new(puzzle).question_fourteen(),
+            ),
+            Clue::reading(
+                "question_seventeen",
+                &[IdentifierVector::down(2)],
+                |puzzle| QRules::new(puzzle).question_seventeen(),
+            ),
+        ]
     }
 
-    /// 3 Across "see" 5 Across, unknown how to implement this :(
-    // fn question_twenty_two(&self) -> bool {
-    //     // Get the digits of 3 Across and 5 Across
-    //     let three_across_digits = self.puzzle.numbers_at(PuzzlePosition::new_across(3));
-    //     let five_across_digits = self.puzzle.numbers_at(PuzzlePosition::new_across(5));
-
-    //     five_across_digits[1..] == three_across_digits
-    //         || five_across_digits[..2] == three_across_digits
-    // }
-
-    /// Apply all the rules for Q and store whether it was successful
-    pub fn apply(&self, is_valid: &mut bool) {
-        *is_valid = self.question_seven()
-            && self.question_nine()
-            && self.question_fourteen()
-            && self.question_seventeen();
-        // && self.question_twenty_two();
+    /// Get every reading of the clues for Q that are unclear, as it is unknown what "sees" means
+    pub fn ambiguous_clues() -> Vec<AmbiguousClue> {
+        let positions = [IdentifierVector::across(3), IdentifierVector::across(5)];
+
+        vec![AmbiguousClue::new(
+            "Q",
+            "question_twenty_two",
+            vec![
+                Interpretation::new(
+                    "appears_in",
+                    Clue::reading("question_twenty_two", &positions, |puzzle| {
+                        QRules::new(puzzle).question_twenty_two_appears_in()
+                    }),
+                ),
+                Interpretation::new(
+                    "reversed_in",
+                    Clue::reading("question_twenty_two", &positions, |puzzle| {
+                        QRules::new(puzzle).question_twenty_two_reversed_in()
+                    }),
+                ),
+                Interpretation::new(
+                    "shares_digit",
+                    Clue::reading("question_twenty_two", &positions, |puzzle| {
+                        QRules::new(puzzle).question_twenty_two_shares_digit()
+                    }),
+                ),
+            ],
+        )]
     }
 }
@@ -1,8 +1,10 @@
-use crate::{
-    lookup_tables::LookupTables,
-    puzzle::{position::PuzzlePosition, Puzzle},
+use ritangle_core::{
+    lookup_tables::{primes::PrimeTable, square::SquareTable},
+    puzzle::position::IdentifierVector,
 };
 
+use crate::{puzzle::Puzzle, rules::clue::Clue};
+
 /// The rules that a puzzle must follow to be valid as R
 pub struct RRules<'a> {
     puzzle: &'a Puzzle,
@@ -10,28 +12,26 @@ pub struct RRules<'a> {
 
 impl<'a> RRules<'a> {
     /// Create a new rule set to be applied on a puzzle for R
-    pub fn new(puzzle: &'a Puzzle) -> RRules {
+    pub fn new(puzzle: &'a Puzzle) -> RRules<'a> {
         RRules { puzzle }
     }
 
     /// Two down is the sum of (the digit product of 1 Across) and (the digit product of 1 Down)
     fn question_eleven(&self) -> bool {
         // Get 2 Down
-        let two_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(2));
+        let two_down = self.puzzle.joined_numbers_at(IdentifierVector::down(2));
 
         // Get 1 Across and 1 Down and find their products
         let one_across_digits: usize = self
             .puzzle
-            .numbers_at(PuzzlePosition::new_across(1))
+            .numbers_at(IdentifierVector::across(1))
             .into_iter()
-            .map(|n| n as usize)
             .product();
 
         let one_down_digits: usize = self
             .puzzle
-            .numbers_at(PuzzlePosition::new_down(1))
+            .numbers_at(IdentifierVector::down(1))
             .into_iter()
-            .map(|n| n as usize)
             .product();
 
         // Check if two down is the sum of the digit products
@@ -41,8 +41,8 @@ impl<'a> RRules<'a> {
     /// For R, 1 Down has the same digit sum as 2 Down
     fn question_fifteen(&self) -> bool {
         // Get the two columns as digits
-        let one_down_digits = self.puzzle.numbers_at(PuzzlePosition::new_down(1));
-        let two_down_digits = self.puzzle.numbers_at(PuzzlePosition::new_down(2));
+        let one_down_digits = self.puzzle.numbers_at(IdentifierVector::down(1));
+        let two_down_digits = self.puzzle.numbers_at(IdentifierVector::down(2));
 
         // Find the sum of their digits
         let one_down_sum = one_down_digits.iter().sum::<usize>();
@@ -54,23 +54,25 @@ impl<'a> RRules<'a> {
     /// For R, 4 Down is equal to 5 Across - any square
     fn question_sixteen(&self) -> bool {
         // Get 4 Down and 5 Across
-        let four_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(4));
-        let five_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(5));
+        let four_down = self.puzzle.joined_numbers_at(IdentifierVector::down(4));
+        let five_across = self.puzzle.joined_numbers_at(IdentifierVector::across(5));
 
         // Rearrange the formula to give,
         // Any square = 5 Across - 4 Down
-        let any_square = five_across - four_down;
+        let Some(any_square) = five_across.checked_sub(four_down) else {
+            return false;
+        };
 
-        LookupTables::is_square(any_square)
+        SquareTable::is_square(any_square)
     }
 
     /// For R, 3 Across is 4 Down - the digit sum of 4 Down
     fn question_nineteen(&self) -> bool {
         // Create a position for 4 Down
-        let four_down_position = PuzzlePosition::new_down(4);
+        let four_down_position = IdentifierVector::down(4);
 
         // Get 3 Across and 4 Down
-        let three_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(3));
+        let three_across = self.puzzle.joined_numbers_at(IdentifierVector::across(3));
         let four_down = self.puzzle.joined_numbers_at(four_down_position);
 
         // Get 4 down as the sum of digits
@@ -81,14 +83,14 @@ impl<'a> RRules<'a> {
             .sum::<usize>();
 
         // 3 Across is 4 Down - the digit sum of 4 Down
-        three_across == (four_down - four_down_sum)
+        four_down.checked_sub(four_down_sum) == Some(three_across)
     }
 
     /// 1 Across has the same digit sum as 1 Down
     fn question_twenty_three(&self) -> bool {
         // Get 1 Across and 1 Down as digits
-        let one_across_digits = self.puzzle.numbers_at(PuzzlePosition::new_across(1));
-        let one_down_digits = self.puzzle.numbers_at(PuzzlePosition::new_down(1));
+        let one_across_digits = self.puzzle.numbers_at(IdentifierVector::across(1));
+        let one_down_digits = self.puzzle.numbers_at(IdentifierVector::down(1));
 
         // Find the sum of their digits
         let one_down_sum = one_down_digits.iter().sum::<usize>();
@@ -102,20 +104,50 @@ impl<'a> RRules<'a> {
         // Get 5 Across as digit sum
         let five_across = self
             .puzzle
-            .numbers_at(PuzzlePosition::new_across(5))
+            .numbers_at(IdentifierVector::across(5))
             .into_iter()
             .sum::<usize>();
 
-        LookupTables::is_prime(five_across)
+        PrimeTable::is_prime(five_across)
     }
 
-    /// Apply all the rules for Q and store whether it was successful
-    pub fn apply(&self, is_valid: &mut bool) {
-        *is_valid = self.question_eleven()
-            && self.question_fifteen()
-            && self.question_sixteen()
-            && self.question_nineteen()
-            && self.question_twenty_three()
-            && self.question_twenty_six();
+    /// Get every clue for R along with the positions each clue reads
+    pub fn clues() -> Vec<Clue> {
+        vec![
+            Clue::reading(
+                "question_eleven",
+                &[
+                    IdentifierVector::down(2),
+                    IdentifierVector::across(1),
+                    IdentifierVector::down(1),
+                ],
+                |puzzle| RRules::new(puzzle).question_eleven(),
+            ),
+            Clue::reading(
+                "question_fifteen",
+                &[IdentifierVector::down(1), IdentifierVector::down(2)],
+                |puzzle| RRules::new(puzzle).question_fifteen(),
+            ),
+            Clue::reading(
+                "question_sixteen",
+                &[IdentifierVector::down(4), IdentifierVector::across(5)],
+                |puzzle| RRules::new(puzzle).question_sixteen(),
+            ),
+            Clue::reading(
+                "question_nineteen",
+                &[IdentifierVector::across(3), IdentifierVector::down(4)],
+                |puzzle| RRules::new(puzzle).question_nineteen(),
+            ),
+            Clue::reading(
+                "question_twenty_three",
+                &[IdentifierVector::across(1), IdentifierVector::down(1)],
+                |puzzle| RRules::new(puzzle).question_twenty_three(),
+            ),
+            Clue::reading(
+                "question_twenty_six",
+                &[IdentifierVector::across(5)],
+                |puzzle| RRules::new(puzzle).question_twenty_six(),
+            ),
+        ]
     }
 }
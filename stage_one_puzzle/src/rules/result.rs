@@ -1,54 +1,93 @@
 use core::fmt;
 use std::fmt::Debug;
 
+use ritangle_core::scan::checkpoint::ScanRecord;
+
 /// A struct to store whether the current puzzle is valid
-/// for the 4 different puzzles
+/// for each of the variants of the puzzle
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct ValidationResult {
-    results: [bool; 4],
+    /// Bit i is set if the puzzle is valid for variant i
+    valid: u64,
+    num_variants: usize,
 }
 
 impl ValidationResult {
+    /// The most variants a result can store
+    pub const MAX_VARIANTS: usize = u64::BITS as usize;
+
     /// Create a new result that sets all results to false
-    pub fn new_invalid() -> Self {
+    pub fn new_invalid(num_variants: usize) -> Self {
+        assert!(num_variants <= Self::MAX_VARIANTS);
+
         Self {
-            results: [false; 4],
+            valid: 0,
+            num_variants,
         }
     }
 
-    pub fn p_mut(&mut self) -> &mut bool {
-        &mut self.results[0]
-    }
+    /// Set whether the puzzle is valid for the variant
+    pub fn set_valid(&mut self, variant: usize, is_valid: bool) {
+        assert!(variant < self.num_variants);
 
-    pub fn q_mut(&mut self) -> &mut bool {
-        &mut self.results[1]
+        if is_valid {
+            self.valid |= 1 << variant;
+        } else {
+            self.valid &= !(1 << variant);
+        }
     }
 
-    pub fn r_mut(&mut self) -> &mut bool {
-        &mut self.results[2]
+    /// Check if the puzzle is valid for the variant
+    pub fn is_valid(&self, variant: usize) -> bool {
+        self.valid & (1 << variant) != 0
     }
 
-    pub fn s_mut(&mut self) -> &mut bool {
-        &mut self.results[3]
+    /// Returns the result of the validation in the order the variants are registered
+    pub fn results(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.num_variants).map(|variant| self.is_valid(variant))
     }
 
-    /// Returns the result of the validation in the order of \[P, Q, R, S\]
-    pub fn results(&self) -> [bool; 4] {
-        self.results
+    /// Get the indexes of every variant the puzzle is valid for
+    pub fn valid_variants(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.num_variants).filter(|&variant| self.is_valid(variant))
     }
 
     // Checks if this puzzle fits the rules for any kind of puzzle
     pub fn is_any_valid(&self) -> bool {
-        self.results.iter().any(|val| *val)
+        self.valid != 0
     }
 }
 
 impl Debug for ValidationResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ValidationResult")
-            .field("is_p_valid", &self.results[0])
-            .field("is_q_valid", &self.results[1])
-            .field("is_r_valid", &self.results[2])
-            .field("is_s_valid", &self.results[3])
+            .field("results", &self.results().collect::<Vec<_>>())
             .finish()
     }
 }
+
+/// Saved in a scan checkpoint as a 1 or a 0 for each variant, such as 0100 for a grid only valid as Q
+impl ScanRecord for ValidationResult {
+    fn to_record(&self) -> String {
+        self.results()
+            .map(|is_valid| if is_valid { '1' } else { '0' })
+            .collect()
+    }
+
+    fn from_record(record: &str) -> Option<Self> {
+        if record.len() > Self::MAX_VARIANTS {
+            return None;
+        }
+
+        let mut result = Self::new_invalid(record.len());
+        for (variant, is_valid) in record.chars().enumerate() {
+            match is_valid {
+                '1' => result.set_valid(variant, true),
+                '0' => {}
+                _ => return None,
+            }
+        }
+
+        Some(result)
+    }
+}
@@ -1,8 +1,10 @@
-use crate::{
-    lookup_tables::LookupTables,
-    puzzle::{position::PuzzlePosition, Puzzle},
+use ritangle_core::{
+    lookup_tables::{primes::PrimeTable, triangular::TriangularTable},
+    puzzle::position::IdentifierVector,
 };
 
+use crate::{puzzle::Puzzle, rules::clue::Clue};
+
 /// The rules that a puzzle must follow to be valid as S
 pub struct SRules<'a> {
     puzzle: &'a Puzzle,
@@ -10,22 +12,21 @@ pub struct SRules<'a> {
 
 impl<'a> SRules<'a> {
     /// Create a new rule set to be applied on a puzzle for S
-    pub fn new(puzzle: &'a Puzzle) -> SRules {
+    pub fn new(puzzle: &'a Puzzle) -> SRules<'a> {
         SRules { puzzle }
     }
 
     /// 1 Down is equal to the 1 Across digit sum squared + 3 Across
     fn question_thirteen(&self) -> bool {
         // Get 1 Down and 3 Across
-        let one_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(1));
-        let three_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(3));
+        let one_down = self.puzzle.joined_numbers_at(IdentifierVector::down(1));
+        let three_across = self.puzzle.joined_numbers_at(IdentifierVector::across(3));
 
         // Get the digit sum squared of 1 Across
         let one_across_squared_sum = self
             .puzzle
-            .numbers_at(PuzzlePosition::new_across(1))
+            .numbers_at(IdentifierVector::across(1))
             .into_iter()
-            .map(|n| n as usize)
             .sum::<usize>()
             .pow(2);
 
@@ -35,29 +36,29 @@ impl<'a> SRules<'a> {
     /// For S, 4 Down is triangular
     fn question_fifteen(&self) -> bool {
         // Get 4 Down
-        let four_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(4));
+        let four_down = self.puzzle.joined_numbers_at(IdentifierVector::down(4));
 
-        LookupTables::is_triangular(four_down)
+        TriangularTable::is_triangular(four_down)
     }
 
     /// For S, 3 Across is Any Triangular - 4 Down
     fn question_sixteen(&self) -> bool {
         // Get 3 Across and 4 Down
-        let three_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(3));
-        let four_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(4));
+        let three_across = self.puzzle.joined_numbers_at(IdentifierVector::across(3));
+        let four_down = self.puzzle.joined_numbers_at(IdentifierVector::down(4));
 
         // Rearrenge the equation to form
         // Any Triangular = 3 Across + 4 Down
         let any_triangular = three_across + four_down;
 
-        LookupTables::is_triangular(any_triangular)
+        TriangularTable::is_triangular(any_triangular)
     }
 
     /// For S, 2 Down is the reverse of 3 Across
     fn question_nineteen(&self) -> bool {
         // Get 2 Down and 3 Across as digits
-        let two_down_digits = self.puzzle.numbers_at(PuzzlePosition::new_down(2));
-        let mut three_across_digits = self.puzzle.numbers_at(PuzzlePosition::new_across(3));
+        let two_down_digits = self.puzzle.numbers_at(IdentifierVector::down(2));
+        let mut three_across_digits = self.puzzle.numbers_at(IdentifierVector::across(3));
 
         // Reverse 3 Across
         three_across_digits.reverse();
@@ -69,10 +70,10 @@ impl<'a> SRules<'a> {
     /// 5 Across is the sum of 1 Down, 3 Across, and 4 Down
     fn question_twenty_four(&self) -> bool {
         // Get all rows and columns
-        let five_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(5));
-        let one_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(1));
-        let three_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(3));
-        let four_down = self.puzzle.joined_numbers_at(PuzzlePosition::new_down(4));
+        let five_across = self.puzzle.joined_numbers_at(IdentifierVector::across(5));
+        let one_down = self.puzzle.joined_numbers_at(IdentifierVector::down(1));
+        let three_across = self.puzzle.joined_numbers_at(IdentifierVector::across(3));
+        let four_down = self.puzzle.joined_numbers_at(IdentifierVector::down(4));
 
         // 5ac = 1dn + 3ac + 4dn
         five_across == (one_down + three_across + four_down)
@@ -80,19 +81,52 @@ impl<'a> SRules<'a> {
 
     /// 1 Across is composite
     fn question_twenty_seven(&self) -> bool {
-        let one_across = self.puzzle.joined_numbers_at(PuzzlePosition::new_across(1));
+        let one_across = self.puzzle.joined_numbers_at(IdentifierVector::across(1));
 
         // A composite number is a number that has more than two numbers (therefore, not a prime)
-        !LookupTables::is_prime(one_across)
+        !PrimeTable::is_prime(one_across)
     }
 
-    /// Apply all the rules for Q and store whether it was successful
-    pub fn apply(&self, is_valid: &mut bool) {
-        *is_valid = self.question_thirteen()
-            && self.question_fifteen()
-            && self.question_sixteen()
-            && self.question_nineteen()
-            && self.question_twenty_four()
-            && self.question_twenty_seven();
+    /// Get every clue for S along with the positions each clue reads
+    pub fn clues() -> Vec<Clue> {
+        vec![
+            Clue::reading(
+                "question_thirteen",
+                &[
+                    IdentifierVector::down(1),
+                    IdentifierVector::across(3),
+                    IdentifierVector::across(1),
+                ],
+                |puzzle| SRules::new(puzzle).question_thirteen(),
+            ),
+            Clue::reading("question_fifteen", &[IdentifierVector::down(4)], |puzzle| {
+                SRules::new(puzzle).question_fifteen()
+            }),
+            Clue::reading(
+                "question_sixteen",
+                &[IdentifierVector::across(3), IdentifierVector::down(4)],
+                |puzzle| SRules::new(puzzle).question_sixteen(),
+            ),
+            Clue::reading(
+                "question_nineteen",
+                &[IdentifierVector::down(2), IdentifierVector::across(3)],
+                |puzzle| SRules::new(puzzle).question_nineteen(),
+            ),
+            Clue::reading(
+                "question_twenty_four",
+                &[
+                    IdentifierVector::across(5),
+                    IdentifierVector::down(1),
+                    IdentifierVector::across(3),
+                    IdentifierVector::down(4),
+                ],
+                |puzzle| SRules::new(puzzle).question_twenty_four(),
+            ),
+            Clue::reading(
+                "question_twenty_seven",
+                &[IdentifierVector::across(1)],
+                |puzzle| SRules::new(puzzle).question_twenty_seven(),
+            ),
+        ]
     }
 }
@@ -0,0 +1,38 @@
+use crate::puzzle::Puzzle;
+
+/// A clue that links the grids of every variant together, such as every value of N being distinct.
+/// It reads one value from each grid, so grids of a variant with the same value are only tried once,
+/// and it is checked on the values chosen so far in the order the variants are registered,
+/// so it must fail for every further choice of values once it has failed
+#[derive(Clone, Copy)]
+pub struct SharedConstraint {
+    name: &'static str,
+    value: fn(&Puzzle) -> usize,
+    check: fn(&[usize]) -> bool,
+}
+
+impl SharedConstraint {
+    /// Create a new constraint on the value read from the grid of each variant
+    pub fn new(
+        name: &'static str,
+        value: fn(&Puzzle) -> usize,
+        check: fn(&[usize]) -> bool,
+    ) -> Self {
+        Self { name, value, check }
+    }
+
+    /// Get the name of the question this constraint comes from
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get the value this constraint reads from the grid
+    pub fn value(&self, grid: &Puzzle) -> usize {
+        (self.value)(grid)
+    }
+
+    /// Check if the values chosen so far, one for each of the first variants, follow this constraint
+    pub fn is_satisfied(&self, values: &[usize]) -> bool {
+        (self.check)(values)
+    }
+}
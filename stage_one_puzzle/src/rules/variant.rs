@@ -0,0 +1,73 @@
+use itertools::Itertools;
+
+use crate::{
+    puzzle::Puzzle,
+    rules::{clue::Clue, interpretation::Interpretation},
+};
+
+/// A variant of the grid, such as P, along with the clues only that variant must follow
+#[derive(Clone)]
+pub struct Variant {
+    name: &'static str,
+    clues: Vec<Clue>,
+    /// The readings chosen for ambiguous clues, whose clues are also in the list of clues
+    readings: Vec<Interpretation>,
+}
+
+impl Variant {
+    /// Create a new variant with the clues it must follow
+    pub fn new(name: &'static str, clues: Vec<Clue>) -> Self {
+        Self {
+            name,
+            clues,
+            readings: Vec::new(),
+        }
+    }
+
+    /// Get the name of the variant
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Add another clue the variant must follow
+    pub fn add_clue(&mut self, clue: Clue) {
+        self.clues.push(clue);
+    }
+
+    /// Add a reading of an ambiguous clue the variant must follow
+    pub fn add_reading(&mut self, interpretation: Interpretation) {
+        self.add_clue(interpretation.clue());
+        self.readings.push(interpretation);
+    }
+
+    /// Describe the clues of the variant on one line, such as Q(question_four,question_twenty_two as appears_in),
+    /// as readings of the same clue share its name
+    pub fn fingerprint(&self) -> String {
+        let readings = self.readings.iter().map(|interpretation| {
+            format!(
+                "{} as {}",
+                interpretation.clue().name(),
+                interpretation.reading()
+            )
+        });
+
+        // The clue of each reading is at the end of the list of clues
+        let clues = self.clues[..self.clues.len() - self.readings.len()]
+            .iter()
+            .map(|clue| clue.name().to_string())
+            .chain(readings)
+            .join(",");
+
+        format!("{}({})", self.name, clues)
+    }
+
+    /// Get the clues of the variant
+    pub fn clues(&self) -> &[Clue] {
+        &self.clues
+    }
+
+    /// Check if the puzzle follows every clue of the variant
+    pub fn is_valid(&self, puzzle: &Puzzle) -> bool {
+        self.clues.iter().all(|clue| clue.is_satisfied(puzzle))
+    }
+}
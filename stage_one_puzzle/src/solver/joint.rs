@@ -0,0 +1,256 @@
+use itertools::Itertools;
+
+use crate::{puzzle::Puzzle, rules::shared::SharedConstraint};
+
+/// The grids of a variant that have the same value for every shared constraint
+struct ValueGroup {
+    values: Vec<usize>,
+    grids: Vec<Puzzle>,
+}
+
+/// A choice of values for every variant that keeps the shared constraints,
+/// along with every grid of each variant that has the values chosen for it
+#[derive(Clone, Debug)]
+pub struct JointSolution {
+    values: Vec<Vec<usize>>,
+    grids: Vec<Vec<Puzzle>>,
+}
+
+impl JointSolution {
+    /// Get the values chosen for the variant, one for each shared constraint in the order they are registered
+    pub fn values(&self, variant: usize) -> &[usize] {
+        &self.values[variant]
+    }
+
+    /// Get the grids of every variant that have the values chosen for it
+    pub fn grids(&self) -> &[Vec<Puzzle>] {
+        &self.grids
+    }
+
+    /// Get every way of choosing one grid for each variant, which all keep the shared constraints
+    pub fn combinations(&self) -> impl Iterator<Item = Vec<Puzzle>> + '_ {
+        self.grids
+            .iter()
+            .map(|grids| grids.iter().cloned())
+            .multi_cartesian_product()
+    }
+}
+
+/// Solves the grids of every variant as one problem, choosing one value of each shared constraint
+/// for every variant so that every constraint holds. Grids with the same values are solved together,
+/// so the search only grows with the number of distinct values rather than the number of grids
+pub struct JointSolver {
+    constraints: Vec<SharedConstraint>,
+}
+
+impl JointSolver {
+    /// Create a new solver for the constraints shared between the variants
+    pub fn new(constraints: Vec<SharedConstraint>) -> Self {
+        Self { constraints }
+    }
+
+    /// Find every choice of values for each variant from the grids valid for that variant,
+    /// in the order the values first appear in the grids provided
+    pub fn solve(&self, candidates: &[Vec<Puzzle>]) -> Vec<JointSolution> {
+        let groups: Vec<Vec<ValueGroup>> = candidates
+            .iter()
+            .map(|grids| self.group_by_values(grids))
+            .collect();
+
+        let mut solutions = Vec::new();
+
+        self.choose(
+            &groups,
+            &mut Vec::with_capacity(groups.len()),
+            &mut solutions,
+        );
+
+        solutions
+    }
+
+    /// Group the grids by the value each shared constraint reads from them, keeping the order of the grids
+    fn group_by_values(&self, grids: &[Puzzle]) -> Vec<ValueGroup> {
+        let mut groups: Vec<ValueGroup> = Vec::new();
+
+        for grid in grids {
+            let values: Vec<usize> = self
+                .constraints
+                .iter()
+                .map(|constraint| constraint.value(grid))
+                .collect();
+
+            // There are only a handful of distinct values, so searching them is quick enough
+            match groups.iter_mut().find(|group| group.values == values) {
+                Some(group) => group.grids.push(grid.clone()),
+                None => groups.push(ValueGroup {
+                    values,
+                    grids: vec![grid.clone()],
+                }),
+            }
+        }
+
+        groups
+    }
+
+    /// Check if the groups chosen so far, one for each of the first variants, keep every shared constraint
+    fn is_satisfied(&self, groups: &[Vec<ValueGroup>], chosen: &[usize]) -> bool {
+        self.constraints
+            .iter()
+            .enumerate()
+            .all(|(constraint_index, constraint)| {
+                let values: Vec<usize> = chosen
+                    .iter()
+                    .enumerate()
+                    .map(|(variant, &group)| groups[variant][group].values[constraint_index])
+                    .collect();
+
+                constraint.is_satisfied(&values)
+            })
+    }
+
+    /// Try every group of values for the next variant that keeps the shared constraints
+    fn choose(
+        &self,
+        groups: &[Vec<ValueGroup>],
+        chosen: &mut Vec<usize>,
+        solutions: &mut Vec<JointSolution>,
+    ) {
+        let variant = chosen.len();
+
+        if variant == groups.len() {
+            // There are values for every variant, so expand them back into their grids
+            let chosen_groups = chosen
+                .iter()
+                .enumerate()
+                .map(|(variant, &group)| &groups[variant][group]);
+
+            solutions.push(JointSolution {
+                values: chosen_groups
+                    .clone()
+                    .map(|group| group.values.clone())
+                    .collect(),
+                grids: chosen_groups.map(|group| group.grids.clone()).collect(),
+            });
+            return;
+        }
+
+        for group in 0..groups[variant].len() {
+            chosen.push(group);
+
+            // Only continue if no shared constraint has been broken so far
+            if self.is_satisfied(groups, chosen) {
+                self.choose(groups, chosen, solutions);
+            }
+
+            chosen.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::JointSolver;
+    use crate::{puzzle::Puzzle, rules::RuleEnforcer};
+
+    /// Create a grid with the value of N provided
+    fn grid_with_n(n: usize) -> Puzzle {
+        Puzzle::from_digits([1, 1, 1, 1, n, 1, 1, 1, 1])
+    }
+
+    /// Join every digit of the grid into one number, so grids can be compared
+    fn grid_number(grid: &Puzzle) -> usize {
+        grid.numbers()
+            .iter()
+            .fold(0, |total, &digit| (total * 10) + digit)
+    }
+
+    #[test]
+    fn test_distinct_n() {
+        let solver = JointSolver::new(RuleEnforcer::shared_constraints());
+
+        // Every value of N must be distinct, which leaves two of the eight choices
+        let candidates = vec![
+            vec![grid_with_n(1), grid_with_n(2)],
+            vec![grid_with_n(2), grid_with_n(3)],
+            vec![grid_with_n(1), grid_with_n(3)],
+        ];
+
+        let n_values: Vec<Vec<usize>> = solver
+            .solve(&candidates)
+            .iter()
+            .map(|solution| (0..3).map(|variant| solution.values(variant)[0]).collect())
+            .collect();
+
+        assert_eq!(n_values, vec![vec![1, 2, 3], vec![2, 3, 1]]);
+    }
+
+    #[test]
+    fn test_matches_cartesian_product() {
+        let solver = JointSolver::new(RuleEnforcer::shared_constraints());
+
+        // Twenty grids for each of four variants, with the last variant only having N of 1 or 2
+        let candidates: Vec<Vec<Puzzle>> = (0..4)
+            .map(|variant| {
+                (0..20)
+                    .map(|i| {
+                        let n = if variant == 3 {
+                            i % 2 + 1
+                        } else {
+                            (i * (variant + 1)) % 4 + 1
+                        };
+
+                        Puzzle::from_digits([variant + 1, i / 10 + 1, i % 10, 1, n, 1, 1, 1, 1])
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Try every combination of grids, then keep those with distinct values of N
+        let product: Vec<(Vec<usize>, Vec<usize>)> = candidates
+            .iter()
+            .map(|grids| grids.iter().cloned())
+            .multi_cartesian_product()
+            .filter(|grids| grids.iter().map(Puzzle::n_digit).all_unique())
+            .map(|grids| {
+                (
+                    grids.iter().map(Puzzle::n_digit).collect(),
+                    grids.iter().map(grid_number).collect(),
+                )
+            })
+            .collect();
+
+        let solutions = solver.solve(&candidates);
+
+        // The same combinations of N are found
+        let product_n_combos: Vec<&Vec<usize>> = product
+            .iter()
+            .map(|(n_combo, _)| n_combo)
+            .unique()
+            .sorted()
+            .collect();
+
+        let n_combos: Vec<Vec<usize>> = solutions
+            .iter()
+            .map(|solution| (0..4).map(|variant| solution.values(variant)[0]).collect())
+            .sorted()
+            .collect();
+
+        assert!(!n_combos.is_empty());
+        assert!(n_combos.iter().eq(product_n_combos));
+
+        // Expanding the solutions back into grids gives the same combinations of grids
+        let product_grids: Vec<&Vec<usize>> =
+            product.iter().map(|(_, grids)| grids).sorted().collect();
+
+        let grids: Vec<Vec<usize>> = solutions
+            .iter()
+            .flat_map(|solution| solution.combinations())
+            .map(|grids| grids.iter().map(grid_number).collect())
+            .sorted()
+            .collect();
+
+        assert!(grids.iter().eq(product_grids));
+    }
+}
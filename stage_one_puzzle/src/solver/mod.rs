@@ -0,0 +1,376 @@
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    puzzle::{layout::StageOneLayout, Puzzle},
+    rules::{clue::Clue, result::ValidationResult, variant::Variant, RuleEnforcer},
+};
+
+pub mod joint;
+
+/// A backtracking solver that fills the grid one cell at a time, reading across each row,
+/// and checks every clue as soon as all of the cells it reads are known
+pub struct Solver {
+    /// The layout of the grid being filled
+    layout: &'static StageOneLayout,
+    /// The clues every kind of puzzle must follow, grouped by the last cell each clue reads
+    base_clues: Vec<Vec<Clue>>,
+    /// The clues for each variant of the puzzle, grouped by the last cell each clue reads
+    variant_clues: Vec<Vec<Vec<Clue>>>,
+    /// The variants of the puzzle, used to validate each complete grid in full
+    variants: Vec<Variant>,
+    /// How close in kilometres a location can be to the boundary of the continental United States
+    border_tolerance: f64,
+}
+
+impl Solver {
+    /// Create a new solver for the clues of every registered kind of puzzle
+    pub fn new() -> Self {
+        Self::with_variants(RuleEnforcer::variants().to_vec())
+    }
+
+    /// Create a new solver for the clues of the provided variants of this year's grid
+    pub fn with_variants(variants: Vec<Variant>) -> Self {
+        Self::with_layout(StageOneLayout::get(), variants)
+    }
+
+    /// Create a new solver for the clues of the provided variants of a grid with the layout
+    pub fn with_layout(layout: &'static StageOneLayout, variants: Vec<Variant>) -> Self {
+        assert!(variants.len() <= ValidationResult::MAX_VARIANTS);
+
+        let num_cells = layout.grid().num_cells();
+
+        Self {
+            layout,
+            base_clues: Self::group_by_last_cell(RuleEnforcer::base_clues(layout), num_cells),
+            variant_clues: variants
+                .iter()
+                .map(|variant| Self::group_by_last_cell(variant.clues().to_vec(), num_cells))
+                .collect(),
+            variants,
+            border_tolerance: 0.0,
+        }
+    }
+
+    /// Accept locations within the distance in kilometres of the boundary of the continental United States
+    pub fn with_border_tolerance(mut self, border_tolerance: f64) -> Self {
+        self.border_tolerance = border_tolerance;
+        self
+    }
+
+    /// Group the clues by the last cell they need, as that is when they can first be checked
+    fn group_by_last_cell(clues: Vec<Clue>, num_cells: usize) -> Vec<Vec<Clue>> {
+        let mut groups = vec![Vec::new(); num_cells];
+
+        for clue in clues {
+            groups[clue.last_cell()].push(clue);
+        }
+
+        groups
+    }
+
+    /// Find every grid that is valid for at least one kind of puzzle,
+    /// in the same order as counting through every possible grid
+    pub fn solve(&self) -> Vec<(Puzzle, ValidationResult)> {
+        // Split the search on the first digit of the grid
+        (0..10)
+            .into_par_iter()
+            .flat_map_iter(|first_digit| self.solve_from(&[first_digit]))
+            .collect()
+    }
+
+    /// Find every valid grid that begins with the digits, reading across each row,
+    /// in the same order as counting through every possible grid
+    pub fn solve_from(&self, prefix: &[usize]) -> Vec<(Puzzle, ValidationResult)> {
+        assert!(!prefix.is_empty() && prefix.len() <= self.base_clues.len());
+
+        let mut digits = vec![0; self.base_clues.len()];
+        digits[..prefix.len()].copy_from_slice(prefix);
+
+        // Every kind of puzzle is still possible before any clue has been checked
+        let mut possible_kinds =
+            (0..self.variant_clues.len()).fold(0, |set, kind| set | (1 << kind));
+
+        // Check the clues of the digits given before the last one, which is checked when it is placed
+        for cell in 0..prefix.len() - 1 {
+            possible_kinds = self.remaining_kinds(&digits, cell, possible_kinds);
+        }
+
+        let mut solutions = Vec::new();
+        if possible_kinds != 0 {
+            self.place(
+                &mut digits,
+                prefix.len() - 1,
+                possible_kinds,
+                &mut solutions,
+            );
+        }
+
+        solutions
+    }
+
+    /// Check the clues completed by the digit in the cell,
+    /// returning the kinds of puzzle that are still possible
+    fn remaining_kinds(&self, digits: &[usize], cell: usize, mut possible_kinds: u64) -> u64 {
+        // Any cells after this one are unknown, but no clue checked here reads them
+        let puzzle = Puzzle::with_layout(self.layout, digits.to_vec());
+
+        // If a base clue fails then no kind of puzzle can be valid
+        if !self.base_clues[cell]
+            .iter()
+            .all(|clue| clue.is_satisfied(&puzzle))
+        {
+            return 0;
+        }
+
+        // Remove every kind of puzzle that has a failing clue
+        for (kind, clues) in self.variant_clues.iter().enumerate() {
+            let kind_bit = 1 << kind;
+
+            if possible_kinds & kind_bit != 0
+                && !clues[cell].iter().all(|clue| clue.is_satisfied(&puzzle))
+            {
+                possible_kinds &= !kind_bit;
+            }
+        }
+
+        possible_kinds
+    }
+
+    /// Check the clues completed by the digit just placed in the cell,
+    /// then try every digit in the next cell for the kinds of puzzle that are still possible
+    fn place(
+        &self,
+        digits: &mut [usize],
+        cell: usize,
+        possible_kinds: u64,
+        solutions: &mut Vec<(Puzzle, ValidationResult)>,
+    ) {
+        let possible_kinds = self.remaining_kinds(digits, cell, possible_kinds);
+
+        if possible_kinds == 0 {
+            // Nothing can be valid past this point
+            return;
+        }
+
+        if cell == digits.len() - 1 {
+            // The grid is complete, so validate it in full to include the location
+            let puzzle = Puzzle::with_layout(self.layout, digits.to_vec());
+            let result = RuleEnforcer::new(&puzzle)
+                .with_border_tolerance(self.border_tolerance)
+                .apply_rules(&self.variants);
+
+            if result.is_any_valid() {
+                solutions.push((puzzle, result));
+            }
+
+            return;
+        }
+
+        // Try every digit in the next cell
+        for digit in 0..10 {
+            digits[cell + 1] = digit;
+
+            self.place(digits, cell + 1, possible_kinds, solutions);
+        }
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+    use ritangle_core::{
+        lookup_tables::primes::PrimeTable,
+        puzzle::{
+            layout::GridLayout,
+            position::{GridPosition, IdentifierVector, PuzzleIdentifier},
+        },
+    };
+
+    use super::Solver;
+    use crate::{
+        puzzle::{layout::StageOneLayout, Puzzle},
+        rules::{clue::Clue, variant::Variant, RuleEnforcer},
+    };
+
+    /// A wider grid with four columns and three rows, where 1, 5 and 6 across fill the rows
+    static WIDE_LAYOUT: LazyLock<StageOneLayout> = LazyLock::new(|| {
+        StageOneLayout::new(
+            GridLayout::new(
+                4,
+                3,
+                vec![
+                    (PuzzleIdentifier(1), GridPosition::new(0, 0)),
+                    (PuzzleIdentifier(2), GridPosition::new(1, 0)),
+                    (PuzzleIdentifier(3), GridPosition::new(2, 0)),
+                    (PuzzleIdentifier(4), GridPosition::new(3, 0)),
+                    (PuzzleIdentifier(5), GridPosition::new(0, 1)),
+                    (PuzzleIdentifier(6), GridPosition::new(0, 2)),
+                ],
+                vec![],
+            ),
+            vec![
+                ('A', GridPosition::new(0, 0)),
+                ('B', GridPosition::new(1, 0)),
+                ('C', GridPosition::new(2, 0)),
+                ('D', GridPosition::new(3, 0)),
+                ('E', GridPosition::new(0, 1)),
+                ('F', GridPosition::new(1, 1)),
+                ('G', GridPosition::new(2, 1)),
+                ('H', GridPosition::new(3, 1)),
+                ('N', GridPosition::new(1, 2)),
+            ],
+        )
+    });
+
+    #[test]
+    fn test_solver_finds_every_grid() {
+        let solutions = Solver::new().solve();
+
+        // Every grid found by scanning every possible grid, for each kind of puzzle in turn
+        let expected_grids = [
+            vec![[3, 6, 1, 6, 3, 2, 4, 6, 8]],
+            vec![
+                [3, 5, 3, 4, 3, 2, 3, 5, 7],
+                [3, 5, 3, 4, 3, 2, 3, 6, 7],
+                [3, 5, 3, 4, 3, 2, 3, 7, 7],
+                [3, 5, 3, 4, 3, 2, 3, 8, 7],
+                [3, 5, 3, 4, 3, 2, 3, 9, 7],
+                [3, 7, 3, 6, 1, 2, 2, 6, 7],
+                [3, 7, 3, 6, 1, 2, 2, 7, 7],
+                [3, 7, 3, 6, 1, 2, 2, 8, 7],
+                [3, 7, 3, 6, 1, 2, 2, 9, 7],
+                [3, 7, 3, 6, 3, 2, 2, 6, 7],
+                [3, 7, 3, 6, 3, 2, 2, 7, 7],
+                [3, 7, 3, 6, 3, 2, 2, 8, 7],
+                [3, 7, 3, 6, 3, 2, 2, 9, 7],
+            ],
+            vec![[3, 8, 1, 5, 4, 5, 4, 5, 8]],
+            vec![[2, 7, 5, 2, 2, 7, 3, 2, 8]],
+        ];
+
+        for (kind, expected) in expected_grids.iter().enumerate() {
+            let mut grids: Vec<Vec<usize>> = solutions
+                .iter()
+                .filter(|(_, result)| result.is_valid(kind))
+                .map(|(puzzle, _)| puzzle.numbers().to_vec())
+                .collect();
+            grids.sort();
+
+            assert_eq!(&grids, expected, "variant {}", kind);
+        }
+    }
+
+    #[test]
+    fn test_solver_matches_scan() {
+        let solver = Solver::new();
+
+        // Scan every grid that begins with the first three digits of each solution
+        for prefix in [[2, 7, 5], [3, 5, 3], [3, 6, 1], [3, 7, 3], [3, 8, 1]] {
+            let start = prefix
+                .iter()
+                .fold(0, |total, &digit| (total * 10) + digit as u64)
+                * 1_000_000;
+
+            let scanned: Vec<_> = (start..start + 1_000_000)
+                .into_par_iter()
+                .map(Puzzle::new)
+                .filter_map(|puzzle| {
+                    let result = RuleEnforcer::new(&puzzle).apply_all_rules();
+
+                    result
+                        .is_any_valid()
+                        .then(|| (puzzle.numbers().to_vec(), result))
+                })
+                .collect();
+
+            let solved: Vec<_> = solver
+                .solve_from(&prefix)
+                .into_iter()
+                .map(|(puzzle, result)| (puzzle.numbers().to_vec(), result))
+                .collect();
+
+            assert!(!scanned.is_empty());
+            assert!(solved == scanned, "{:?}", prefix);
+        }
+    }
+
+    #[test]
+    fn test_border_tolerance() {
+        let inside = Solver::new().solve_from(&[3, 7]);
+        let borderline = Solver::new()
+            .with_border_tolerance(50.0)
+            .solve_from(&[3, 7]);
+
+        // Each solver keeps its own tolerance, and a wider one only adds grids near the boundary
+        assert!(borderline.len() > inside.len());
+        assert!(inside.iter().all(|(puzzle, _)| borderline
+            .iter()
+            .any(|(other, _)| other.numbers() == puzzle.numbers())));
+    }
+
+    #[test]
+    fn test_solver_with_other_layout() {
+        let layout: &'static StageOneLayout = &WIDE_LAYOUT;
+        let cells = |position| {
+            layout
+                .grid()
+                .entry_cells(position)
+                .expect("The position should be an entry of the grid")
+                .to_vec()
+        };
+
+        let variant = Variant::new(
+            "wide",
+            vec![
+                Clue::new(
+                    "one_across",
+                    &cells(IdentifierVector::across(1)),
+                    |puzzle| puzzle.joined_numbers_at(IdentifierVector::across(1)) == 3612,
+                ),
+                Clue::new(
+                    "five_across",
+                    &cells(IdentifierVector::across(5)),
+                    |puzzle| puzzle.joined_numbers_at(IdentifierVector::across(5)) == 8646,
+                ),
+                Clue::new(
+                    "six_across",
+                    &cells(IdentifierVector::across(6)),
+                    |puzzle| {
+                        let number = puzzle.joined_numbers_at(IdentifierVector::across(6));
+                        number.isqrt().pow(2) == number
+                    },
+                ),
+                Clue::new("four_down", &cells(IdentifierVector::down(4)), |puzzle| {
+                    PrimeTable::is_prime(puzzle.joined_numbers_at(IdentifierVector::down(4)))
+                }),
+            ],
+        );
+
+        let mut grids: Vec<Vec<usize>> = Solver::with_layout(layout, vec![variant])
+            .solve()
+            .into_iter()
+            .map(|(puzzle, _)| puzzle.numbers().to_vec())
+            .collect();
+        grids.sort();
+
+        // The bottom row is a square ending in 9 so 4 down is 269, with N between 1 and 4
+        let expected: Vec<Vec<usize>> = [1369, 2209, 3249, 4489, 5329, 9409]
+            .iter()
+            .map(|square| {
+                let mut grid = vec![3, 6, 1, 2, 8, 6, 4, 6];
+                grid.extend(square.to_string().bytes().map(|b| (b - b'0') as usize));
+                grid
+            })
+            .collect();
+
+        assert_eq!(grids, expected);
+    }
+}
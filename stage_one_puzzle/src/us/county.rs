@@ -0,0 +1,123 @@
+use std::{
+    fmt::{self, Display},
+    path::Path,
+};
+
+use super::{UnitedStatesLookup, STATE_FIPS_PROPERTY};
+use crate::region::{
+    source::{RegionError, RegionSource},
+    Region, RegionLookup,
+};
+
+/// A county of the United States along with the state it is in
+#[derive(Clone, Copy, Debug)]
+pub struct County<'a> {
+    region: &'a Region,
+}
+
+impl<'a> County<'a> {
+    /// Get the name of the county, such as Davidson
+    pub fn name(&self) -> &'a str {
+        self.region.name()
+    }
+
+    /// Get the five digit FIPS code of the county, such as 47037
+    pub fn fips(&self) -> &'a str {
+        self.region
+            .code()
+            .expect("A county should be loaded with its FIPS code")
+    }
+
+    /// Get the two digit FIPS code of the state the county is in, such as 47
+    pub fn state_fips(&self) -> &'a str {
+        self.region
+            .property(STATE_FIPS_PROPERTY)
+            .expect("A county should be loaded with the FIPS code of its state")
+    }
+
+    /// Get the state the county is in, if it is in the continental United States
+    pub fn state(&self) -> Option<&'static Region> {
+        UnitedStatesLookup::state_with_fips(self.state_fips())
+    }
+
+    /// Check if the county has the FIPS code or the name provided, ignoring case.
+    /// Many counties share a name, such as Jefferson, so the FIPS code is needed to pick out a single county
+    pub fn is_named(&self, name_or_fips: &str) -> bool {
+        self.fips() == name_or_fips || self.name().eq_ignore_ascii_case(name_or_fips)
+    }
+}
+
+impl Display for County<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name(), self.fips())
+    }
+}
+
+/// The counties of the United States, loaded from a Census county boundary file such as cb_2018_us_county_20m
+pub struct CountyLookup {
+    counties: RegionLookup,
+}
+
+impl CountyLookup {
+    /// The properties the Census county boundary files name each county with
+    fn source() -> RegionSource {
+        RegionSource::new("NAME")
+            .with_code_property("GEOID")
+            .keeping(&[STATE_FIPS_PROPERTY])
+    }
+
+    /// Load the counties from a GeoJSON file
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, RegionError> {
+        Ok(Self {
+            counties: RegionLookup::from_path(path, &Self::source())?,
+        })
+    }
+
+    /// Load the counties from the text of a GeoJSON feature collection
+    pub fn from_geojson(text: &str) -> Result<Self, RegionError> {
+        Ok(Self {
+            counties: RegionLookup::from_geojson(text, &Self::source())?,
+        })
+    }
+
+    /// Find the county containing the GPS coordinate, where longitude represents east and latitude represents north
+    pub fn county_at(&self, longitude: f64, latitude: f64) -> Option<County<'_>> {
+        self.counties
+            .region_at(longitude, latitude)
+            .map(|region| County { region })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CountyLookup;
+
+    /// Davidson County in Tennessee and Jefferson County in both Kentucky and Alabama,
+    /// with each boundary simplified to a rectangle
+    const COUNTIES: &str = include_str!("fixtures/counties.geojson");
+
+    #[test]
+    fn test_county_at() {
+        let counties = CountyLookup::from_geojson(COUNTIES).unwrap();
+
+        // Nashville
+        let county = counties.county_at(-86.7667, 36.2).unwrap();
+
+        assert_eq!(county.to_string(), "Davidson (47037)");
+        assert_eq!(county.state().and_then(|state| state.code()), Some("TN"));
+
+        // Louisville and Birmingham are both in a Jefferson County
+        let louisville = counties.county_at(-85.75, 38.25).unwrap();
+        let birmingham = counties.county_at(-86.8104, 33.5186).unwrap();
+
+        assert!(louisville.is_named("jefferson") && birmingham.is_named("Jefferson"));
+        assert!(louisville.is_named("21111") && !birmingham.is_named("21111"));
+        assert_eq!(
+            birmingham.state().map(|state| state.name()),
+            Some("Alabama")
+        );
+
+        // Tampa is not in any of the counties
+        assert!(counties.county_at(-82.5333, 27.95).is_none());
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+    sync::OnceLock,
+};
+
+use ritangle_core::geography::great_circle::Position;
+
+/// The places of at least 100,000 people in the 2010 Census Gazetteer places file,
+/// keeping only the columns that are read and with the locations rounded, as made by
+/// awk -F'\t' -v OFS='\t' 'NR == 1 { print $1, $4, $5, $11, $12 } NR > 1 && $5 >= 100000 { printf "%s\t%s\t%s\t%.2f\t%.2f\n", $1, $4, $5, $11, $12 }' 2010_Gaz_place_national.txt
+const CENSUS_PLACES: &str = include_str!("places.txt");
+
+/// A named place, such as a city or a town
+#[derive(Clone, Debug, PartialEq)]
+pub struct Place {
+    name: String,
+    state: String,
+    latitude: f64,
+    longitude: f64,
+    population: Option<u32>,
+}
+
+impl Place {
+    /// Get the name of the place
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the two letter USPS code of the state the place is in, such as TN
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Get the latitude of the place, where north is positive
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /// Get the longitude of the place, where east is positive
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Get the population of the place, which is only known when the file has a count
+    pub fn population(&self) -> Option<u32> {
+        self.population
+    }
+}
+
+impl Display for Place {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.name, self.state)
+    }
+}
+
+/// The reasons a gazetteer cannot be read, lines are counted from 1 including the header
+#[derive(Debug)]
+pub enum GazetteerError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The header does not have the column
+    MissingColumn(&'static str),
+    /// The line does not have the column
+    MissingField { line: usize, field: &'static str },
+    /// The column on the line is not a number
+    InvalidNumber { line: usize, field: &'static str },
+}
+
+impl Display for GazetteerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GazetteerError::Io(error) => write!(f, "Could not read the places file: {}", error),
+            GazetteerError::MissingColumn(column) => {
+                write!(f, "The header has no {} column", column)
+            }
+            GazetteerError::MissingField { line, field } => {
+                write!(f, "Line {} has no {}", line, field)
+            }
+            GazetteerError::InvalidNumber { line, field } => {
+                write!(f, "The {} on line {} is not a number", field, line)
+            }
+        }
+    }
+}
+
+impl Error for GazetteerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GazetteerError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GazetteerError {
+    fn from(error: io::Error) -> Self {
+        GazetteerError::Io(error)
+    }
+}
+
+/// Where each field of a place is in the lines of a Census Gazetteer file
+struct Columns {
+    state: usize,
+    name: usize,
+    latitude: usize,
+    longitude: usize,
+    population: Option<usize>,
+}
+
+impl Columns {
+    /// Find the columns from the header, whose names may be padded with spaces
+    fn from_header(header: &str) -> Result<Self, GazetteerError> {
+        let names: Vec<&str> = header.split('\t').map(str::trim).collect();
+
+        let find = |column| names.iter().position(|&name| name == column);
+        let require = |column| find(column).ok_or(GazetteerError::MissingColumn(column));
+
+        Ok(Self {
+            state: require("USPS")?,
+            name: require("NAME")?,
+            latitude: require("INTPTLAT")?,
+            longitude: require("INTPTLONG")?,
+            // Only the 2010 files count the people in each place
+            population: find("POP10"),
+        })
+    }
+}
+
+/// A list of named places which can be searched for the place nearest to a location
+#[derive(Clone, Debug)]
+pub struct Gazetteer {
+    places: Vec<Place>,
+}
+
+impl Gazetteer {
+    /// Get the embedded places of at least 100,000 people from the 2010 Census
+    pub fn census() -> &'static Gazetteer {
+        static PLACES: OnceLock<Gazetteer> = OnceLock::new();
+
+        PLACES.get_or_init(|| {
+            Self::from_census(CENSUS_PLACES).expect("The embedded Census places should be valid")
+        })
+    }
+
+    /// Load the places from a Census Gazetteer places file, such as 2020_Gaz_place_national.txt
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, GazetteerError> {
+        // The older files are Latin-1, which only differs from UTF-8 in the accents of a few names
+        let bytes = fs::read(path)?;
+
+        Self::from_census(&String::from_utf8_lossy(&bytes))
+    }
+
+    /// Read the places from the tab separated text of a Census Gazetteer places file,
+    /// which needs the USPS, NAME, INTPTLAT and INTPTLONG columns and may have POP10
+    pub fn from_census(text: &str) -> Result<Self, GazetteerError> {
+        let mut lines = text.lines();
+        let columns = Columns::from_header(lines.next().unwrap_or_default())?;
+
+        let places = lines
+            .enumerate()
+            // Skip any blank lines, counting the header as line 1
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| Self::read_place(&columns, index + 2, line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { places })
+    }
+
+    /// Read a place from a line of the file
+    fn read_place(columns: &Columns, line: usize, text: &str) -> Result<Place, GazetteerError> {
+        let fields: Vec<&str> = text.split('\t').map(str::trim).collect();
+
+        let field = |index: usize, field| {
+            fields
+                .get(index)
+                .copied()
+                .ok_or(GazetteerError::MissingField { line, field })
+        };
+        let invalid = |field| GazetteerError::InvalidNumber { line, field };
+
+        Ok(Place {
+            name: field(columns.name, "name")?.to_string(),
+            state: field(columns.state, "state")?.to_string(),
+            latitude: field(columns.latitude, "latitude")?
+                .parse()
+                .map_err(|_| invalid("latitude"))?,
+            longitude: field(columns.longitude, "longitude")?
+                .parse()
+                .map_err(|_| invalid("longitude"))?,
+            population: match columns.population {
+                Some(index) => Some(
+                    field(index, "population")?
+                        .parse()
+                        .map_err(|_| invalid("population"))?,
+                ),
+                None => None,
+            },
+        })
+    }
+
+    /// Get every place in the gazetteer
+    pub fn places(&self) -> &[Place] {
+        &self.places
+    }
+
+    /// Find the place closest to the location along with its distance in kilometres,
+    /// where longitude represents east and latitude represents north
+    pub fn nearest(&self, longitude: f64, latitude: f64) -> Option<(&Place, f64)> {
+        let location = Position::new(latitude, longitude);
+
+        self.places
+            .iter()
+            .map(|place| {
+                let distance =
+                    location.haversine_distance(&Position::new(place.latitude, place.longitude));
+
+                (place, distance)
+            })
+            .min_by(|(_, first), (_, second)| first.total_cmp(second))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gazetteer, GazetteerError};
+
+    /// A few places in the layout of the 2010 Census Gazetteer places file,
+    /// keeping only the columns that are read and with the locations rounded
+    const PLACES: &str = include_str!("fixtures/places.txt");
+
+    #[test]
+    fn test_nearest() {
+        let gazetteer = Gazetteer::from_census(PLACES).unwrap();
+
+        assert_eq!(gazetteer.places().len(), 6);
+        assert_eq!(gazetteer.places()[0].population(), Some(600158));
+
+        // The State Capitol in Denver, and Pearl Street in Boulder
+        let (place, distance) = gazetteer.nearest(-104.9848, 39.7392).unwrap();
+
+        assert_eq!(place.to_string(), "Denver city, CO");
+        assert!((9.0..10.0).contains(&distance), "{} km", distance);
+
+        let (place, _) = gazetteer.nearest(-105.2799, 40.0176).unwrap();
+        assert_eq!(place.name(), "Boulder city");
+
+        // The two Portlands are on opposite coasts
+        let (place, _) = gazetteer.nearest(-70.25, 43.66).unwrap();
+        assert_eq!(place.to_string(), "Portland city, ME");
+
+        let (place, _) = gazetteer.nearest(-122.68, 45.52).unwrap();
+        assert_eq!(place.to_string(), "Portland city, OR");
+    }
+
+    #[test]
+    fn test_census() {
+        let gazetteer = Gazetteer::census();
+
+        assert_eq!(gazetteer.places().len(), 280);
+        assert!(gazetteer
+            .places()
+            .iter()
+            .all(|place| place.population() >= Some(100_000)));
+
+        // The locations of P, Q, R and S are the places that make up the final code
+        for (longitude, latitude, name) in [
+            (
+                -86.7667,
+                36.2,
+                "Nashville-Davidson metropolitan government (balance), TN",
+            ),
+            (-77.4333, 37.5333, "Richmond city, VA"),
+            (
+                -85.75,
+                38.25,
+                "Louisville/Jefferson County metro government (balance), KY",
+            ),
+            (-82.5333, 27.95, "Tampa city, FL"),
+        ] {
+            let (place, distance) = gazetteer.nearest(longitude, latitude).unwrap();
+
+            assert_eq!(place.to_string(), name);
+            assert!(distance < 15.0, "{} km from {}", distance, name);
+        }
+    }
+
+    #[test]
+    fn test_without_population() {
+        // The newer files have no population column, and pad the last column name
+        let gazetteer = Gazetteer::from_census(
+            "USPS\tGEOID\tNAME\tINTPTLAT\tINTPTLONG         \n\
+             WA\t5363000\tSeattle city\t47.6205\t-122.3509\n",
+        )
+        .unwrap();
+
+        assert_eq!(gazetteer.places()[0].name(), "Seattle city");
+        assert_eq!(gazetteer.places()[0].population(), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Gazetteer::from_census("USPS\tNAME\tINTPTLAT\n"),
+            Err(GazetteerError::MissingColumn("INTPTLONG"))
+        ));
+        assert!(matches!(
+            Gazetteer::from_census("USPS\tNAME\tINTPTLAT\tINTPTLONG\nCO\tDenver city\t39.76\n"),
+            Err(GazetteerError::MissingField {
+                line: 2,
+                field: "longitude"
+            })
+        ));
+        assert!(matches!(
+            Gazetteer::from_census(
+                "USPS\tNAME\tINTPTLAT\tINTPTLONG\nCO\tDenver city\tnorth\t-104.88\n"
+            ),
+            Err(GazetteerError::InvalidNumber {
+                line: 2,
+                field: "latitude"
+            })
+        ));
+        assert!(matches!(
+            Gazetteer::from_path("no_such_places.txt"),
+            Err(GazetteerError::Io(_))
+        ));
+    }
+}
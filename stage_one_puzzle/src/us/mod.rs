@@ -1,91 +1,205 @@
-use std::sync::OnceLock;
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+    sync::OnceLock,
+};
+
+use crate::region::{
+    source::{RegionError, RegionSource},
+    Containment, Region, RegionLookup,
+};
+
+pub mod county;
+pub mod gazetteer;
 
-use geo::{Point, Within};
-use geojson::FeatureCollection;
-use state::State;
+/// Store the contents of the GeoJSON file containing the US State boundaries here
+const STATE_GEOJSON: &str = include_str!("cb_2018_us_state_20m.geojson");
 
-pub mod state;
+/// The property of the Census boundary files giving the two digit FIPS code of the state
+const STATE_FIPS_PROPERTY: &str = "STATEFP";
 
-/// Store the contents of the GeoJSON file containing the US State boundaries here
-const STATE_GEOJSON: &'static str = include_str!("cb_2018_us_state_20m.geojson");
+/// Every state in continental United States, from the embedded file unless another file is loaded first
+static STATES: OnceLock<RegionLookup> = OnceLock::new();
+
+/// The reasons the state boundaries cannot be loaded from a file
+#[derive(Debug)]
+pub enum StatesError {
+    /// The file is not a usable boundary file
+    Region(RegionError),
+    /// The states were already loaded, so every location has to be checked against them
+    AlreadyLoaded,
+}
+
+impl Display for StatesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatesError::Region(error) => write!(f, "{}", error),
+            StatesError::AlreadyLoaded => write!(
+                f,
+                "The state boundaries were already loaded, they must be loaded before any location is checked"
+            ),
+        }
+    }
+}
+
+impl Error for StatesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StatesError::Region(error) => Some(error),
+            StatesError::AlreadyLoaded => None,
+        }
+    }
+}
+
+impl From<io::Error> for StatesError {
+    fn from(error: io::Error) -> Self {
+        StatesError::Region(RegionError::Io(error))
+    }
+}
+
+impl From<RegionError> for StatesError {
+    fn from(error: RegionError) -> Self {
+        StatesError::Region(error)
+    }
+}
 
 pub struct UnitedStatesLookup;
 
 impl UnitedStatesLookup {
-    /// Get every state in continental United States as list of its name and geometry
-    fn all_continental_states() -> &'static Vec<State> {
-        static GEO_DATA: OnceLock<Vec<State>> = OnceLock::new();
-
-        GEO_DATA.get_or_init(|| {
-            // Parse US states information from  GeoJSON file
-            let geo_data = STATE_GEOJSON.parse().unwrap();
-
-            let FeatureCollection {
-                bbox: _,
-                features,
-                foreign_members: _,
-            } = geo_data;
-
-            // Go through each feature, which is a US state and get its geometry and name
-            let mut states = Vec::with_capacity(64);
-            for feature in features {
-                // We know the state name should exist so these unwraps are okay
-                let properties = feature.properties.unwrap();
-                let mut state_name = properties
-                    .get("NAME")
-                    .unwrap()
-                    .as_str()
-                    .unwrap()
-                    .to_string();
-
-                // Make the state name lowercase as its easier to match and the puzzle needs it later
-                state_name.make_ascii_lowercase();
-
-                // We want to exclude alaska and hawaii because they are not part of the continental United States
-                // We also exclude Puerto Rico as its not a state
-                if ["alaska", "hawaii", "puerto rico"].contains(&&*state_name) {
-                    continue;
-                }
-
-                // Get state geometry
-                let state_geometry = feature.geometry.unwrap();
-
-                states.push(State::new(state_name, state_geometry.try_into().unwrap()));
-            }
+    /// Get every state in continental United States along with an index over their bounding boxes
+    fn states() -> &'static RegionLookup {
+        STATES.get_or_init(|| {
+            Self::states_from_geojson(STATE_GEOJSON)
+                .expect("The embedded US State boundaries should be valid")
+        })
+    }
 
-            // let mut test_output = File::create_new("a.geojson").unwrap();
-            // test_output
-            //     .write_all(
-            //         FeatureCollection::from(&GeometryCollection::from_iter(
-            //             states
-            //                 .iter()
-            //                 .map(|state| state.geometry().clone())
-            //                 .collect::<Vec<geo::Geometry>>(),
-            //         ))
-            //         .to_string()
-            //         .as_bytes(),
-            //     )
-            //     .unwrap();
+    /// Read the states in continental United States from the text of a Census state boundary file
+    fn states_from_geojson(text: &str) -> Result<RegionLookup, RegionError> {
+        // We want to exclude alaska and hawaii because they are not part of the continental United States
+        // We also exclude Puerto Rico as its not a state
+        let source = RegionSource::new("NAME")
+            .with_code_property("STUSPS")
+            .keeping(&[STATE_FIPS_PROPERTY])
+            .excluding(&["Alaska", "Hawaii", "Puerto Rico"]);
 
-            states
-        })
+        RegionLookup::from_geojson(text, &source)
+    }
+
+    /// Use the states from a Census state boundary file such as cb_2018_us_state_500k instead of the embedded file,
+    /// which has to be done before any location is checked
+    pub fn load_states(path: impl AsRef<Path>) -> Result<(), StatesError> {
+        // Check first so a large file is not read for nothing
+        if STATES.get().is_some() {
+            return Err(StatesError::AlreadyLoaded);
+        }
+
+        let states = Self::states_from_geojson(&fs::read_to_string(path)?)?;
+
+        STATES.set(states).map_err(|_| StatesError::AlreadyLoaded)
+    }
+
+    /// Find where the GPS coordinate is relative to the continental United States,
+    /// where points within the tolerance in kilometres of a state boundary or the coast are borderline.
+    /// The boundaries are simplified to 1:20 million, so points close to them can land on the wrong side
+    pub fn classify(longitude: f64, latitude: f64, tolerance: f64) -> Containment<'static> {
+        Self::states().classify(longitude, latitude, tolerance)
+    }
+
+    /// Find the state in the continental United States with the two digit FIPS code, such as 47 for Tennessee
+    pub fn state_with_fips(fips: &str) -> Option<&'static Region> {
+        Self::states()
+            .regions()
+            .iter()
+            .find(|state| state.property(STATE_FIPS_PROPERTY) == Some(fips))
     }
 
     /// Check to see if the GPS coordinate is within the continental United State where longitude represents east and latitude represents north
     /// (This means it excludes Hawaii, Alaska, and Puerto Rico from the search)
-    pub fn is_within_us<'a>(longitude: f64, latitude: f64) -> bool {
-        let test_point = Point::new(longitude, latitude);
-        for state in Self::all_continental_states() {
-            // Get geometry
-            let state_geometry = state.geometry();
-
-            // Check if point is within geometry
-            if test_point.is_within(state_geometry) {
-                return true;
+    pub fn is_within_us(longitude: f64, latitude: f64) -> bool {
+        Self::states().contains(longitude, latitude)
+    }
+
+    /// Find the state in the continental United States containing the GPS coordinate,
+    /// where longitude represents east and latitude represents north
+    pub fn state_at(longitude: f64, latitude: f64) -> Option<&'static Region> {
+        Self::states().region_at(longitude, latitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::{Point, Within};
+
+    use super::{StatesError, UnitedStatesLookup};
+
+    /// Tennessee, Kentucky and Alaska, with each boundary simplified to a rectangle
+    const STATES: &str = include_str!("fixtures/states.geojson");
+
+    #[test]
+    fn test_state_at() {
+        // Nashville
+        let state = UnitedStatesLookup::state_at(-86.7667, 36.2).unwrap();
+
+        assert_eq!(state.name(), "Tennessee");
+        assert_eq!(state.code(), Some("TN"));
+
+        // The Atlantic Ocean and Honolulu, which is not part of the continental United States
+        assert!(UnitedStatesLookup::state_at(-60.0, 35.0).is_none());
+        assert!(UnitedStatesLookup::state_at(-157.8583, 21.3069).is_none());
+    }
+
+    #[test]
+    fn test_states_from_geojson() {
+        let states = UnitedStatesLookup::states_from_geojson(STATES).unwrap();
+
+        // Alaska is not part of the continental United States
+        assert_eq!(states.regions().len(), 2);
+        assert_eq!(
+            states
+                .region_at(-85.75, 38.25)
+                .and_then(|state| state.code()),
+            Some("KY")
+        );
+        assert!(states.region_at(-149.9, 61.2).is_none());
+    }
+
+    #[test]
+    fn test_load_states() {
+        // Once a location has been checked the states cannot change under it
+        UnitedStatesLookup::is_within_us(0.0, 0.0);
+
+        assert!(matches!(
+            UnitedStatesLookup::load_states("src/us/fixtures/states.geojson"),
+            Err(StatesError::AlreadyLoaded)
+        ));
+    }
+
+    #[test]
+    fn test_index_matches_scan() {
+        let states = UnitedStatesLookup::states().regions();
+
+        // Check a grid of points every quarter of a degree over the continental United States
+        for longitude_step in 0..240 {
+            for latitude_step in 0..104 {
+                let longitude = -126.0 + longitude_step as f64 * 0.25;
+                let latitude = 24.0 + latitude_step as f64 * 0.25;
+
+                let point = Point::new(longitude, latitude);
+                let scanned = states
+                    .iter()
+                    .find(|state| point.is_within(state.geometry()));
+
+                assert_eq!(
+                    UnitedStatesLookup::state_at(longitude, latitude).map(|state| state.code()),
+                    scanned.map(|state| state.code()),
+                    "Mismatch at {}, {}",
+                    longitude,
+                    latitude
+                );
             }
         }
-
-        // This point is not within a state
-        false
     }
 }
@@ -1,5 +1,5 @@
 fn main() {
-    let all_integers: Vec<usize> = (2..2024).into_iter().collect();
+    let all_integers: Vec<usize> = (2..2024).collect();
 
     let mut product = 1;
 
@@ -1,3 +1,4 @@
+use ritangle_core::exact::recognise::Recogniser;
 use triangle::Triangle;
 
 pub mod point;
@@ -23,4 +24,15 @@ fn main() {
     }
 
     println!("{} {} {}", max_area, t_when_max, max_area * t_when_max);
+
+    // Find the exact values the floats are closest to
+    let recogniser = Recogniser::new();
+    let exact = |x: f64| match recogniser.recognise(x) {
+        Some(exact) => exact.to_string(),
+        None => "no exact form found".to_string(),
+    };
+
+    println!("area = {}", exact(max_area));
+    println!("t = {}", exact(t_when_max));
+    println!("area × t = {}", exact(max_area * t_when_max));
 }
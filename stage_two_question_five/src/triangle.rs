@@ -28,12 +28,30 @@ impl Triangle {
         let perimeter = side_a + side_b + side_c;
         let half_perimeter = perimeter / 2.0;
 
-        let area = (half_perimeter
+        (half_perimeter
             * (half_perimeter - side_a)
             * (half_perimeter - side_b)
             * (half_perimeter - side_c))
-            .sqrt();
+            .sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use ritangle_core::exact::{recognise::Recogniser, Exact};
+
+    use super::Triangle;
+
+    #[test]
+    fn test_largest_area() {
+        let area = Triangle::new(1.25 * PI).area();
+
+        // At the largest area the triangle is equilateral with sides of 1 + 2√2
+        let side = Exact::integer(1) + Exact::integer(2) * Exact::sqrt(2);
+        let equilateral_area = Exact::sqrt(3) * Exact::rational(1, 4) * side.pow(2);
 
-        return area;
+        assert_eq!(Recogniser::new().recognise(area), Some(equilateral_area));
     }
 }
@@ -1,4 +1,4 @@
-use crate::puzzle::Puzzle;
+use ritangle_core::puzzle::Puzzle;
 
 pub struct NumberSoupChallenge;
 
@@ -6,6 +6,6 @@ impl NumberSoupChallenge {
     /// Compute the solution to the triangle problem
     pub fn compute() {
         // Use predefined puzzle as its already been solved
-        let puzzle = Puzzle::new(9, 4, vec![], vec![]);
+        let _puzzle = Puzzle::new(9, 4, vec![], vec![]);
     }
 }
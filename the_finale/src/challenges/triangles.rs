@@ -1,19 +1,18 @@
 use itertools::Itertools;
-
-use crate::{
-    // lookup_tables::pythagorean_triples::PythagoreanTriplesTable,
+use ritangle_core::{
     lookup_tables::pythagorean_triples::PythagoreanTriplesTable,
     puzzle::{
         position::{GridPosition, IdentifierVector, PuzzleDirection, PuzzleIdentifier},
         wall::WallDirection,
         Puzzle,
     },
-    rules::{
-        executor::RuleExecutor,
-        triangles::{
-            pythagorean_triangle::PythagoreanTriangleRule, square::SumSquare,
-            triangle_trio::OddCompositeCoprimeRule,
-        },
+};
+
+use crate::rules::{
+    executor::RuleExecutor,
+    triangles::{
+        pythagorean_triangle::PythagoreanTriangleRule, square::SumSquare,
+        triangle_trio::OddCompositeCoprimeRule,
     },
 };
 
@@ -1,8 +1,6 @@
 use challenges::triangles::TrianglesChallenge;
 
 pub mod challenges;
-pub mod lookup_tables;
-pub mod puzzle;
 pub mod rules;
 
 fn main() {
@@ -1,4 +1,4 @@
-use crate::puzzle::Puzzle;
+use ritangle_core::puzzle::Puzzle;
 
 use super::PuzzleRule;
 
@@ -50,7 +50,7 @@ impl<'a> RuleExecutor<'a> {
 
             // If this was the last rule empty the puzzle buffer into the complete puzzles stack
             if current_rule == self.rules.len() - 1 {
-                complete_puzzles.extend(puzzle_buffer.drain(..));
+                complete_puzzles.append(&mut puzzle_buffer);
                 continue;
             }
 
@@ -1,4 +1,4 @@
-use crate::puzzle::Puzzle;
+use ritangle_core::puzzle::Puzzle;
 
 pub mod executor;
 pub mod triangles;
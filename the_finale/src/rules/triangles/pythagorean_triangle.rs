@@ -1,11 +1,11 @@
 use itertools::Itertools;
-use tinyvec::ArrayVec;
-
-use crate::{
+use ritangle_core::{
     lookup_tables::pythagorean_triples::PythagoreanTriplesTable,
     puzzle::{position::IdentifierVector, Puzzle},
-    rules::PuzzleRule,
 };
+use tinyvec::ArrayVec;
+
+use crate::rules::PuzzleRule;
 
 pub struct PythagoreanTriangleRule(pub [IdentifierVector; 3]);
 
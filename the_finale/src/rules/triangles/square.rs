@@ -1,10 +1,10 @@
-use tinyvec::ArrayVec;
-
-use crate::{
+use ritangle_core::{
     lookup_tables::square::SquareTable,
     puzzle::{position::IdentifierVector, Puzzle},
-    rules::PuzzleRule,
 };
+use tinyvec::ArrayVec;
+
+use crate::rules::PuzzleRule;
 
 pub struct SumSquare(pub [IdentifierVector; 2]);
 
@@ -23,7 +23,7 @@ impl PuzzleRule for SumSquare {
 
         // Go through all potential squares
         for square in squares
-            .into_iter()
+            .iter()
             .copied()
             .filter(|&square| square >= minimum_square)
         {
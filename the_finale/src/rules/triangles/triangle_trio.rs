@@ -1,12 +1,12 @@
 use itertools::Itertools;
 use num_integer::Integer;
-use tinyvec::ArrayVec;
-
-use crate::{
+use ritangle_core::{
     lookup_tables::{coprimes::CoprimesTable, primes::PrimeTable, triangular::TriangularTable},
     puzzle::{position::IdentifierVector, Puzzle},
-    rules::PuzzleRule,
 };
+use tinyvec::ArrayVec;
+
+use crate::rules::PuzzleRule;
 
 /// 3 Sides form a triangle whose perimeter is triangular and whose sides are each odd, composite and co-prime to one another
 pub struct OddCompositeCoprimeRule(pub [IdentifierVector; 3]);